@@ -1,3 +1 @@
-pub mod weather;
-pub mod search;
 pub mod files;
\ No newline at end of file
@@ -0,0 +1,165 @@
+use crate::intent::Intent;
+use crate::normalize::normalize;
+use crate::classifier::{Classifier, ClassificationResult, IntentScore, TrainingSet};
+use crate::spelling::SpellChecker;
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+
+/// Wortzählungen eines Intents
+#[derive(Clone, Serialize, Deserialize)]
+struct ClassStats {
+    intent: Intent,
+    /// Summe der Beispielgewichte (für die A-priori-Wahrscheinlichkeit)
+    doc_weight: f32,
+    counts: HashMap<String, f32>,
+    total: f32,
+}
+
+/// Multinomialer Naive Bayes über normalisierte Wörter (Bag of Words).
+/// Trainiert nur auf Phrasen, reine Vektor-Beispiele werden ignoriert.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NaiveBayes {
+    classes: Vec<ClassStats>,
+    vocab: HashSet<String>,
+    /// Additive Glättung; klein, weil es pro Intent nur wenige Phrasen gibt
+    alpha: f32,
+    #[serde(skip)]
+    spelling: SpellChecker,
+}
+
+impl NaiveBayes {
+    pub fn new() -> Self {
+        Self {
+            classes: Vec::new(),
+            vocab: HashSet::new(),
+            alpha: 0.1,
+            spelling: SpellChecker::default(),
+        }
+    }
+
+    fn tokens(text: &str) -> Vec<String> {
+        normalize(text).split_whitespace().map(str::to_string).collect()
+    }
+}
+
+impl Classifier for NaiveBayes {
+    fn name(&self) -> &'static str {
+        "naive_bayes"
+    }
+
+    fn train(&mut self, data: &TrainingSet) {
+        self.classes.clear();
+        self.vocab.clear();
+        self.spelling = SpellChecker::from_training(data);
+
+        for ex in &data.examples {
+            let idx = match self.classes.iter().position(|c| c.intent == ex.intent) {
+                Some(idx) => idx,
+                None => {
+                    self.classes.push(ClassStats {
+                        intent: ex.intent.clone(),
+                        doc_weight: 0.0,
+                        counts: HashMap::new(),
+                        total: 0.0,
+                    });
+                    self.classes.len() - 1
+                }
+            };
+
+            let class = &mut self.classes[idx];
+            class.doc_weight += ex.weight;
+            for token in Self::tokens(&ex.phrase) {
+                *class.counts.entry(token.clone()).or_insert(0.0) += ex.weight;
+                class.total += ex.weight;
+                self.vocab.insert(token);
+            }
+        }
+    }
+
+    fn predict(&mut self, text: &str) -> ClassificationResult {
+        let (text, corrections) = self.spelling.correct(text);
+        // Unbekannte Wörter tragen nichts zur Unterscheidung bei
+        let tokens: Vec<String> = Self::tokens(&text)
+            .into_iter()
+            .filter(|t| self.vocab.contains(t))
+            .collect();
+
+        let total_docs: f32 = self.classes.iter().map(|c| c.doc_weight).sum();
+        let v = self.vocab.len() as f32;
+
+        let log_posteriors: Vec<f32> = self.classes
+            .iter()
+            .map(|c| {
+                let mut lp = (c.doc_weight / total_docs).ln();
+                for t in &tokens {
+                    let count = c.counts.get(t).copied().unwrap_or(0.0);
+                    lp += ((count + self.alpha) / (c.total + self.alpha * v)).ln();
+                }
+                lp
+            })
+            .collect();
+
+        // Softmax → Wahrscheinlichkeiten
+        let max = log_posteriors.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let exps: Vec<f32> = log_posteriors.iter().map(|lp| (lp - max).exp()).collect();
+        let sum: f32 = exps.iter().sum();
+
+        let candidates = self.classes
+            .iter()
+            .zip(exps)
+            .map(|(c, e)| {
+                let p = e / sum;
+                IntentScore {
+                    intent: c.intent.clone(),
+                    similarity: p,
+                    weight: 1.0,
+                    boost: 1.0,
+                    score: p,
+                    sample_index: None,
+                }
+            })
+            .collect();
+
+        ClassificationResult { corrections, ..ClassificationResult::ranked(candidates) }
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classifier::TrainingExample;
+    use crate::vector::DocStats;
+
+    fn example(intent: Intent, phrase: &str) -> TrainingExample {
+        TrainingExample { intent, phrase: phrase.to_string(), weight: 1.0 }
+    }
+
+    #[test]
+    fn test_naive_bayes_prefers_seen_words() {
+        let data = TrainingSet {
+            examples: vec![
+                example(Intent::Weather, "wie ist das wetter heute"),
+                example(Intent::Weather, "regnet es morgen"),
+                example(Intent::FileOpen, "öffne die datei"),
+                example(Intent::FileOpen, "starte das programm"),
+            ],
+            doc_stats: DocStats::new(),
+        };
+
+        let mut nb = NaiveBayes::new();
+        nb.train(&data);
+
+        let result = nb.predict("regnet es");
+        assert_eq!(result.best().unwrap().intent, Intent::Weather);
+
+        let result = nb.predict("programm starten");
+        assert_eq!(result.best().unwrap().intent, Intent::FileOpen);
+
+        let total: f32 = result.candidates.iter().map(|c| c.similarity).sum();
+        assert!((total - 1.0).abs() < 1e-4);
+    }
+}
@@ -1,79 +1,381 @@
 use crate::intent::Intent;
+use crate::classifier::ClassificationResult;
+use crate::normalize::stem;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::fs;
+use std::time::SystemTime;
 
-pub fn intent_boost(intent: &Intent, text: &str) -> f32 {
-    let t = text.to_lowercase();
-
-    match intent {
-        Intent::Greeting => {
-            // Reduziere Greeting bei Fragen oder Befehlen
-            if t.contains("?") || t.contains("was ist") || t.contains("wer ist") 
-                || t.contains("zeig") || t.contains("öffne") || t.contains("starte") {
-                0.3  // Stark reduzieren bei Fragen/Befehlen
-            } else if t.contains("hallo") || t.contains("hi") || t.contains("hey") || t.contains("guten") {
-                1.5
-            } else {
-                1.0
+pub const FILE: &str = "boost_rules.json";
+
+/// Mitgelieferte Standardregeln (Inhalt von `boost_rules.json` beim Bauen)
+const DEFAULT_RULES: &str = include_str!("../boost_rules.json");
+
+/// Wörter, die einen Teilsatz verneinen ("öffne das nicht")
+const NEGATIONS: &[&str] = &[
+    "nicht", "kein", "keine", "keinen", "keinem", "keiner", "nie", "niemals",
+];
+
+/// Wörter, die Teilsätze trennen (zusätzlich zu Satzzeichen)
+const CLAUSE_BREAKS: &[&str] = &["und", "aber", "sondern", "oder", "dann"];
+
+/// Eine Boost-Regel: trifft ein Keyword (ganzes Wort), eine Phrase (Wortfolge)
+/// oder der Regex zu und nicht die `suppress_when`-Bedingung, wird der
+/// Intent-Score mit `multiplier` multipliziert. Mehrere zutreffende Regeln
+/// werden multipliziert. Treffer in verneinten Teilsätzen zählen nur mit
+/// `match_negated`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoostRule {
+    pub intent: Intent,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+    pub multiplier: f32,
+    #[serde(default)]
+    pub match_negated: bool,
+    #[serde(default)]
+    pub suppress_when: Option<Condition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Bedingung aus Keywords, Phrasen und Regex (eins davon muss zutreffen)
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+}
+
+/// Text zerlegt in Teilsätze aus Wortstämmen, für wortgenauen Abgleich
+/// ("hi" trifft nicht "nicht", "bis" nicht "bisher").
+pub struct MatchText {
+    lower: String,
+    clauses: Vec<Clause>,
+}
+
+struct Clause {
+    stems: Vec<String>,
+    negated: bool,
+}
+
+impl MatchText {
+    pub fn new(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        let mut clauses = Vec::new();
+
+        for part in lower.split([',', '.', ';', ':', '!', '?']) {
+            let mut current = Clause { stems: Vec::new(), negated: false };
+
+            for word in part.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+                // "bis dann" ist ein Abschied, kein neuer Teilsatz
+                let farewell = word == "dann" && current.stems.last().is_some_and(|s| s == "bis");
+                if CLAUSE_BREAKS.contains(&word) && !farewell {
+                    clauses.push(std::mem::replace(&mut current, Clause { stems: Vec::new(), negated: false }));
+                    continue;
+                }
+                if NEGATIONS.contains(&word) {
+                    current.negated = true;
+                }
+                current.stems.push(stem(word));
             }
+            clauses.push(current);
         }
-        Intent::Search => {
-            // Prüfe auf Fragestellung
-            let is_question = t.contains("was ist") || t.contains("wer ist") || t.contains("wie") 
-                || t.contains("warum") || t.contains("wo") || t.contains("?");
-            
-            if t.contains("suche")
-                || t.contains("finde")
-                || t.contains("information")
-                || t.contains("recherch")
-                || t.contains("erkläre")
-                || t.contains("über")
-                || t.contains("wiki")
-                || is_question {
-                2.5  // Erhöht von 2.0 um Fragen stärker zu gewichten
-            } else {
-                1.0
-            }
+
+        clauses.retain(|c| !c.stems.is_empty());
+        Self { lower, clauses }
+    }
+
+    /// Enthält der Text die Wortfolge (ein oder mehrere Wörter)?
+    pub fn contains(&self, phrase: &str, include_negated: bool) -> bool {
+        let wanted: Vec<String> = phrase.to_lowercase().split_whitespace().map(stem).collect();
+        if wanted.is_empty() {
+            return false;
         }
-        Intent::Weather => {
-            if t.contains("wetter")
-                || t.contains("temperatur")
-                || t.contains("warm")
-                || t.contains("kalt")
-                || t.contains("grad")
-                || t.contains("regen")
-                || t.contains("schnee") {
-                1.3
-            } else {
-                1.0
+
+        self.clauses
+            .iter()
+            .filter(|c| include_negated || !c.negated)
+            .any(|c| c.stems.windows(wanted.len()).any(|w| w == wanted.as_slice()))
+    }
+
+    pub fn contains_any(&self, phrases: &[&str]) -> bool {
+        phrases.iter().any(|p| self.contains(p, true))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    rules: Vec<BoostRule>,
+}
+
+struct CompiledCondition {
+    phrases: Vec<String>,
+    regex: Option<Regex>,
+}
+
+impl CompiledCondition {
+    fn matches(&self, text: &MatchText, include_negated: bool) -> bool {
+        self.phrases.iter().any(|p| text.contains(p, include_negated))
+            || self.regex.as_ref().is_some_and(|r| r.is_match(&text.lower))
+    }
+}
+
+struct CompiledRule {
+    rule: BoostRule,
+    condition: CompiledCondition,
+    suppress: Option<CompiledCondition>,
+}
+
+impl CompiledRule {
+    fn applies(&self, text: &MatchText) -> bool {
+        self.condition.matches(text, self.rule.match_negated)
+            && !self.suppress.as_ref().is_some_and(|s| s.matches(text, true))
+    }
+}
+
+#[derive(Debug)]
+pub enum BoostError {
+    Io(String),
+    Parse(String),
+    Invalid { rule: usize, intent: Intent, message: String },
+}
+
+impl fmt::Display for BoostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoostError::Io(e) => write!(f, "{} nicht lesbar: {}", FILE, e),
+            BoostError::Parse(e) => write!(f, "{} ist kein gültiges JSON: {}", FILE, e),
+            BoostError::Invalid { rule, intent, message } => {
+                write!(f, "{}: Regel {} ({:?}): {}", FILE, rule + 1, intent, message)
             }
         }
-        Intent::FileOpen => {
-            if t.contains("öffne") || t.contains("starte") || t.contains("öffnen") || t.contains("start") {
-                1.4
-            } else {
-                1.0
-            }
+    }
+}
+
+/// Geladene Boost-Regeln, werden bei Änderung der Datei neu eingelesen
+pub struct BoostRules {
+    rules: Vec<CompiledRule>,
+    path: String,
+    modified: Option<SystemTime>,
+}
+
+impl Default for BoostRules {
+    fn default() -> Self {
+        let rules = compile(DEFAULT_RULES).expect("Standard-Boost-Regeln sind ungültig");
+        Self { rules, path: FILE.to_string(), modified: None }
+    }
+}
+
+impl BoostRules {
+    /// Lädt die Regeln aus `path`. Fehlt die Datei, gelten die Standardregeln.
+    pub fn load(path: &str) -> Result<Self, BoostError> {
+        let modified = modified(path);
+        let rules = match fs::read_to_string(path) {
+            Ok(data) => compile(&data)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => compile(DEFAULT_RULES)?,
+            Err(e) => return Err(BoostError::Io(e.to_string())),
+        };
+
+        Ok(Self { rules, path: path.to_string(), modified })
+    }
+
+    /// Lädt die Datei neu, falls sie sich seit dem letzten Laden geändert hat.
+    /// Bei Fehlern bleiben die bisherigen Regeln aktiv.
+    pub fn reload_if_changed(&mut self) -> Option<Result<usize, BoostError>> {
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
         }
-        Intent::FileShow => {
-            if t.contains("zeig") || t.contains("wo ist") || t.contains("finde datei") || t.contains("mir") {
-                1.5  // Erhöht von 1.4
-            } else {
-                1.0
-            }
+        self.modified = modified;
+
+        Some(Self::load(&self.path).map(|fresh| {
+            self.rules = fresh.rules;
+            self.rules.len()
+        }))
+    }
+
+    pub fn boost(&self, intent: &Intent, text: &MatchText) -> f32 {
+        self.rules
+            .iter()
+            .filter(|r| &r.rule.intent == intent && r.applies(text))
+            .map(|r| r.rule.multiplier)
+            .product()
+    }
+
+    /// Trifft eine verstärkende Regel des Intents zu? Die GUI erkennt so
+    /// Abschiede mit denselben Wörtern wie der Klassifizierer.
+    pub fn signals(&self, intent: &Intent, text: &MatchText) -> bool {
+        self.rules
+            .iter()
+            .any(|r| &r.rule.intent == intent && r.rule.multiplier > 1.0 && r.applies(text))
+    }
+
+    /// Multipliziert den Boost auf alle Kandidaten und sortiert neu
+    pub fn apply(&self, result: &mut ClassificationResult, text: &str) {
+        let text = MatchText::new(text);
+        for c in &mut result.candidates {
+            c.boost = self.boost(&c.intent, &text);
+            c.score *= c.boost;
         }
-        Intent::Goodbye => {
-            if t.contains("danke") || t.contains("tschüss") || t.contains("bis") {
-                1.3
-            } else {
-                1.0
+        result.sort();
+    }
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn compile_condition(
+    keywords: &[String],
+    phrases: &[String],
+    regex: &Option<String>,
+) -> Result<CompiledCondition, String> {
+    if let Some(k) = keywords.iter().find(|k| k.split_whitespace().count() != 1) {
+        return Err(format!("Keyword '{}' muss genau ein Wort sein (mehrere Wörter unter phrases)", k));
+    }
+    if phrases.iter().any(|p| p.trim().is_empty()) {
+        return Err("leere Phrase".to_string());
+    }
+
+    let regex = match regex {
+        Some(r) => Some(Regex::new(r).map_err(|e| format!("ungültiger Regex '{}': {}", r, e))?),
+        None => None,
+    };
+
+    Ok(CompiledCondition {
+        phrases: keywords.iter().chain(phrases).cloned().collect(),
+        regex,
+    })
+}
+
+fn compile(json: &str) -> Result<Vec<CompiledRule>, BoostError> {
+    let file: RuleFile = serde_json::from_str(json).map_err(|e| BoostError::Parse(e.to_string()))?;
+
+    file.rules
+        .into_iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let invalid = |message: String| BoostError::Invalid {
+                rule: i,
+                intent: rule.intent.clone(),
+                message,
+            };
+
+            if rule.intent == Intent::Unknown {
+                return Err(invalid("Intent Unknown kann nicht geboostet werden".to_string()));
             }
-        }
-        Intent::Shutdown => {
-            if t.contains("schönen") || t.contains("beenden") || t.contains("ausschalten") {
-                1.5
-            } else {
-                1.0
+            if !rule.multiplier.is_finite() || rule.multiplier <= 0.0 {
+                return Err(invalid(format!("multiplier muss > 0 sein (ist {})", rule.multiplier)));
             }
+            if rule.keywords.is_empty() && rule.phrases.is_empty() && rule.regex.is_none() {
+                return Err(invalid("braucht mindestens ein Keyword, eine Phrase oder einen Regex".to_string()));
+            }
+
+            let condition = compile_condition(&rule.keywords, &rule.phrases, &rule.regex)
+                .map_err(&invalid)?;
+            let suppress = match &rule.suppress_when {
+                Some(s) => Some(
+                    compile_condition(&s.keywords, &s.phrases, &s.regex)
+                        .map_err(|e| invalid(format!("suppress_when: {}", e)))?,
+                ),
+                None => None,
+            };
+
+            Ok(CompiledRule { rule, condition, suppress })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let rules = BoostRules::default();
+        assert_eq!(rules.boost(&Intent::Weather, &MatchText::new("Wie ist das Wetter")), 1.3);
+        assert_eq!(rules.boost(&Intent::Greeting, &MatchText::new("Hallo")), 1.5);
+        // Bei Fragen wird die Begrüßung unterdrückt statt verstärkt
+        assert_eq!(rules.boost(&Intent::Greeting, &MatchText::new("Hallo, was ist Rust?")), 0.3);
+        assert_eq!(rules.boost(&Intent::Shutdown, &MatchText::new("xyz")), 1.0);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let err = |json: &str| compile(json).err().unwrap().to_string();
+
+        assert!(err(r#"{"rules": [{"intent": "Weather", "regex": "(", "multiplier": 1.2}]}"#)
+            .contains("Regel 1 (Weather): ungültiger Regex"));
+        assert!(err(r#"{"rules": [{"intent": "Weather", "keywords": ["a"], "multiplier": 0}]}"#)
+            .contains("multiplier"));
+        assert!(err(r#"{"rules": [{"intent": "Weather", "multiplier": 1.2}]}"#)
+            .contains("mindestens ein Keyword"));
+        assert!(err(r#"{"rules": [{"intent": "Weather", "keywords": ["wie warm"], "multiplier": 1.2}]}"#)
+            .contains("genau ein Wort"));
+        assert!(err(r#"{"rules": [{"intent": "Wetter", "keywords": ["a"], "multiplier": 1.2}]}"#)
+            .contains("kein gültiges JSON"));
+    }
+
+    #[test]
+    fn test_regex_rule() {
+        let rules = compile(r#"{"rules": [{"intent": "Weather", "regex": "\\d+ ?grad", "multiplier": 2.0}]}"#).unwrap();
+        let rules = BoostRules { rules, path: FILE.to_string(), modified: None };
+        assert_eq!(rules.boost(&Intent::Weather, &MatchText::new("Sind es 20 Grad?")), 2.0);
+        assert_eq!(rules.boost(&Intent::Weather, &MatchText::new("Grad")), 1.0);
+    }
+
+    /// Bekannte Fehltreffer der alten Substring-Suche
+    #[test]
+    fn test_false_positive_regressions() {
+        let rules = BoostRules::default();
+        let cases: &[(&str, Intent)] = &[
+            ("Das ist nicht wichtig", Intent::Greeting),    // "hi" in "nicht"/"wichtig"
+            ("Das Wort kenne ich", Intent::Search),         // "wo" in "wort"
+            ("Was hast du bisher gemacht", Intent::Goodbye), // "bis" in "bisher"
+            ("Öffne das nicht", Intent::FileOpen),          // verneint
+            ("Bitte nicht starten", Intent::FileOpen),
+            ("Kaltstart", Intent::Weather),                 // "kalt" in "kaltstart"
+        ];
+
+        for (text, intent) in cases {
+            assert_eq!(rules.boost(intent, &MatchText::new(text)), 1.0, "{:?} bei '{}'", intent, text);
         }
-        _ => 1.0,
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_token_matches() {
+        let rules = BoostRules::default();
+        assert_eq!(rules.boost(&Intent::Greeting, &MatchText::new("Hi!")), 1.5);
+        assert_eq!(rules.boost(&Intent::FileOpen, &MatchText::new("Öffnen bitte")), 1.4);
+        assert_eq!(rules.boost(&Intent::Weather, &MatchText::new("Wird es wärmer?")), 1.3);
+        assert_eq!(rules.boost(&Intent::Goodbye, &MatchText::new("Danke, bis bald")), 1.3);
+        assert_eq!(rules.boost(&Intent::FileShow, &MatchText::new("Wo ist mein Ordner")), 1.5);
+        // Verneinung gilt nur im eigenen Teilsatz
+        assert_eq!(rules.boost(&Intent::FileOpen, &MatchText::new("Nicht löschen, sondern öffne die Datei")), 1.4);
+
+        let text = MatchText::new("Was hast du bisher gemacht?");
+        assert!(!text.contains_any(&["bis bald", "bis"]));
+        assert!(text.contains_any(&["du bisher"]));
+    }
+
+    #[test]
+    fn test_signals() {
+        let rules = BoostRules::default();
+        assert!(rules.signals(&Intent::Goodbye, &MatchText::new("Okay, bis dann!")));
+        assert!(rules.signals(&Intent::Goodbye, &MatchText::new("Danke")));
+        assert!(!rules.signals(&Intent::Goodbye, &MatchText::new("Was hast du bisher gemacht?")));
+        // Abschwächende Regeln zählen nicht
+        assert!(!rules.signals(&Intent::Greeting, &MatchText::new("Zeige mir die Datei")));
+    }
+}
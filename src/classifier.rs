@@ -1,42 +1,463 @@
 use crate::intent::Intent;
 use crate::similarity::cosine_similarity;
-use crate::boost::intent_boost;
+use crate::spelling::{Correction, SpellChecker};
+use crate::vector::{DocStats, SentenceEmbedder};
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// Wie die Beispiel-Scores zu einem Intent-Score zusammengefasst werden
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ClassifyMode {
+    /// Bestes einzelnes Beispiel gewinnt
+    #[default]
+    BestSample,
+    /// Die k ähnlichsten Beispiele stimmen ab
+    Knn { k: usize, vote: Vote },
+    /// Vergleich mit dem gewichteten Mittelwert (Prototyp) jedes Intents
+    Centroid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Vote {
+    /// Jeder Nachbar zählt mit seinem Gewicht
+    Sum,
+    /// Nähere Nachbarn zählen stärker
+    DistanceWeighted,
+}
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct IntentSample {
     pub intent: Intent,
     pub vector: Vec<f32>,
     pub weight: f32,
+    /// Ursprüngliche Phrase (fehlt bei älteren gelernten Beispielen)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phrase: Option<String>,
+}
+
+/// Eine Trainingsphrase mit Intent und Gewicht
+#[derive(Clone)]
+pub struct TrainingExample {
+    pub intent: Intent,
+    pub phrase: String,
+    pub weight: f32,
+}
+
+/// Trainingsdaten aus statischen und gelernten Beispielen
+pub struct TrainingSet {
+    pub examples: Vec<TrainingExample>,
+    /// Dokumenthäufigkeiten aller Phrasen
+    pub doc_stats: DocStats,
+}
+
+/// Gemeinsame Schnittstelle aller Intent-Klassifizierer
+pub trait Classifier {
+    fn name(&self) -> &'static str;
+
+    /// Trainiert das Modell neu aus den gegebenen Daten
+    fn train(&mut self, data: &TrainingSet);
+
+    /// Rangliste aller Intents (ohne Keyword-Boost, siehe `BoostRules::apply`)
+    fn predict(&mut self, text: &str) -> ClassificationResult;
+
+    /// Satzvektor einer Phrase, falls der Klassifizierer mit Vektoren
+    /// arbeitet (z.B. zum Erkennen fast gleicher Beispiele)
+    fn embed(&mut self, _text: &str) -> Option<Vec<f32>> {
+        None
+    }
+
+    fn to_json(&self) -> serde_json::Result<String>;
+}
+
+/// Welche Implementierung das Backend nutzt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ClassifierKind {
+    #[default]
+    Cosine,
+    NaiveBayes,
+}
+
+/// Bewertung eines Intents.
+/// Bei kNN ist `weight` der Stimmenanteil der Nachbarn dieses Intents.
+#[derive(Debug, Clone)]
+pub struct IntentScore {
+    pub intent: Intent,
+    pub similarity: f32,
+    pub weight: f32,
+    pub boost: f32,
+    pub score: f32,
+    /// Index des ähnlichsten Beispiels in der übergebenen Sample-Liste
+    /// (`None` bei Modellen ohne einzelne Beispiele)
+    pub sample_index: Option<usize>,
+}
+
+/// Rangliste aller Intents, bester zuerst
+#[derive(Debug, Clone)]
+pub struct ClassificationResult {
+    pub candidates: Vec<IntentScore>,
+    /// Vor der Klassifizierung korrigierte Tippfehler
+    pub corrections: Vec<Correction>,
+}
+
+impl ClassificationResult {
+    pub fn ranked(candidates: Vec<IntentScore>) -> Self {
+        let mut result = Self { candidates, corrections: Vec::new() };
+        result.sort();
+        result
+    }
+
+    pub fn sort(&mut self) {
+        self.candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    }
+
+    pub fn best(&self) -> Option<&IntentScore> {
+        self.candidates.first()
+    }
+
+    pub fn top(&self, n: usize) -> &[IntentScore] {
+        &self.candidates[..n.min(self.candidates.len())]
+    }
+
+    /// Abstand des besten zum zweitbesten Intent
+    pub fn margin(&self) -> f32 {
+        match self.candidates.as_slice() {
+            [best, second, ..] => best.score - second.score,
+            [best] => best.score,
+            [] => 0.0,
+        }
+    }
 }
 
 pub fn classify(
     input_vec: &[f32],
-    original_text: &str,
     samples: &[IntentSample],
-) -> Intent {
-    let mut best_score = -1.0;
-    let mut best_intent = Intent::Unknown;
+    mode: ClassifyMode,
+) -> ClassificationResult {
+    let candidates = match mode {
+        ClassifyMode::BestSample => best_sample_scores(input_vec, samples),
+        ClassifyMode::Knn { k, vote } => knn_scores(input_vec, samples, k, vote),
+        ClassifyMode::Centroid => centroid_scores(input_vec, samples),
+    };
+
+    ClassificationResult::ranked(candidates)
+}
+
+/// Pro Intent das beste Beispiel (score = sim * weight)
+fn best_sample_scores(input_vec: &[f32], samples: &[IntentSample]) -> Vec<IntentScore> {
+    let mut candidates: Vec<IntentScore> = Vec::new();
+
+    for (i, s) in samples.iter().enumerate() {
+        let sim = cosine_similarity(input_vec, &s.vector);
+        let candidate = IntentScore {
+            intent: s.intent.clone(),
+            similarity: sim,
+            weight: s.weight,
+            boost: 1.0,
+            score: sim * s.weight,
+            sample_index: Some(i),
+        };
+
+        match candidates.iter_mut().find(|c| c.intent == s.intent) {
+            Some(existing) if existing.score < candidate.score => *existing = candidate,
+            Some(_) => {}
+            None => candidates.push(candidate),
+        }
+    }
+
+    candidates
+}
+
+/// Die k ähnlichsten Beispiele stimmen ab. Der Score eines Intents ist die
+/// Summe seiner gewichteten Ähnlichkeiten geteilt durch alle Stimmen, ein
+/// einzelnes hoch gewichtetes Beispiel kann so keine Mehrheit überstimmen.
+fn knn_scores(input_vec: &[f32], samples: &[IntentSample], k: usize, vote: Vote) -> Vec<IntentScore> {
+    let mut neighbours: Vec<(usize, f32)> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (i, cosine_similarity(input_vec, &s.vector)))
+        .collect();
+    neighbours.sort_by(|a, b| b.1.total_cmp(&a.1));
+    neighbours.truncate(k.max(1));
+
+    let votes: Vec<f32> = neighbours
+        .iter()
+        .map(|&(i, sim)| {
+            let sim = sim.clamp(0.0, 1.0);
+            match vote {
+                Vote::Sum => samples[i].weight,
+                Vote::DistanceWeighted => samples[i].weight / (1.0 - sim + 0.01),
+            }
+        })
+        .collect();
+    let total: f32 = votes.iter().sum();
+
+    let mut candidates: Vec<IntentScore> = Vec::new();
+    for (&(i, sim), v) in neighbours.iter().zip(&votes) {
+        let share = if total > 0.0 { v / total } else { 0.0 };
+        let intent = &samples[i].intent;
+
+        match candidates.iter_mut().find(|c| &c.intent == intent) {
+            Some(existing) => {
+                existing.weight += share;
+                existing.score += share * sim;
+            }
+            None => candidates.push(IntentScore {
+                intent: intent.clone(),
+                similarity: sim,
+                weight: share,
+                boost: 1.0,
+                score: share * sim,
+                sample_index: Some(i),
+            }),
+        }
+    }
+
+    candidates
+}
+
+/// Vergleicht mit einem Prototyp pro Intent: dem gewichteten Mittel der
+/// normierten Beispielvektoren.
+fn centroid_scores(input_vec: &[f32], samples: &[IntentSample]) -> Vec<IntentScore> {
+    struct Prototype {
+        intent: Intent,
+        sum: Vec<f32>,
+        weight_sum: f32,
+        count: usize,
+        best: (usize, f32),
+    }
 
-    for s in samples {
+    let mut prototypes: Vec<Prototype> = Vec::new();
+    for (i, s) in samples.iter().enumerate() {
+        let norm = s.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
         let sim = cosine_similarity(input_vec, &s.vector);
-        let boost = intent_boost(&s.intent, original_text);
-        let score = sim * s.weight * boost;
 
-        //Debug Ausgabe:
-        //println!("Ähnlichkeit zu {:?}: sim={:.3}, weight={:.2}, boost={:.2}, score={:.3}", s.intent, sim, s.weight, boost, score);
+        let idx = match prototypes.iter().position(|p| p.intent == s.intent) {
+            Some(idx) => idx,
+            None => {
+                prototypes.push(Prototype {
+                    intent: s.intent.clone(),
+                    sum: vec![0.0; s.vector.len()],
+                    weight_sum: 0.0,
+                    count: 0,
+                    best: (i, sim),
+                });
+                prototypes.len() - 1
+            }
+        };
+
+        let p = &mut prototypes[idx];
+        for (acc, x) in p.sum.iter_mut().zip(&s.vector) {
+            *acc += s.weight * x / norm;
+        }
+        p.weight_sum += s.weight;
+        p.count += 1;
+        if sim > p.best.1 {
+            p.best = (i, sim);
+        }
+    }
+
+    prototypes
+        .into_iter()
+        .map(|p| {
+            let sim = cosine_similarity(input_vec, &p.sum);
+            let weight = p.weight_sum / p.count as f32;
+            IntentScore {
+                intent: p.intent,
+                similarity: sim,
+                weight,
+                boost: 1.0,
+                score: sim * weight,
+                sample_index: Some(p.best.0),
+            }
+        })
+        .collect()
+}
+
+/// Kosinus-Ähnlichkeit zu den Beispielvektoren. Woher die Satzvektoren
+/// kommen, bestimmt der `SentenceEmbedder` (Hashing-Vokabular, Wortvektoren
+/// oder Transformer).
+pub struct CosineClassifier {
+    embedder: Box<dyn SentenceEmbedder>,
+    mode: ClassifyMode,
+    samples: Vec<IntentSample>,
+    /// Satzvektoren nach Phrase. Erneutes Training (neue Gewichte, ein
+    /// neues Beispiel) bettet so nur neue Phrasen ein – bei einem
+    /// Transformer ein Modelldurchlauf pro Phrase.
+    vectors: HashMap<String, Vec<f32>>,
+    spelling: SpellChecker,
+}
+
+impl CosineClassifier {
+    pub fn new(embedder: Box<dyn SentenceEmbedder>, mode: ClassifyMode) -> Self {
+        Self { embedder, mode, samples: Vec::new(), vectors: HashMap::new(), spelling: SpellChecker::default() }
+    }
+}
+
+impl Classifier for CosineClassifier {
+    fn name(&self) -> &'static str {
+        "cosine"
+    }
+
+    fn train(&mut self, data: &TrainingSet) {
+        if self.embedder.set_doc_stats(data.doc_stats.clone()) {
+            self.vectors.clear();
+        }
+        self.spelling = SpellChecker::from_training(data);
+
+        let mut samples = Vec::with_capacity(data.examples.len());
+        let mut cached = std::mem::take(&mut self.vectors);
+        for ex in &data.examples {
+            let vector = match self.vectors.get(&ex.phrase) {
+                Some(v) => v.clone(),
+                None => {
+                    let v = cached.remove(&ex.phrase).unwrap_or_else(|| self.embedder.sentence_vec(&ex.phrase));
+                    self.vectors.insert(ex.phrase.clone(), v.clone());
+                    v
+                }
+            };
+            samples.push(IntentSample {
+                intent: ex.intent.clone(),
+                vector,
+                weight: ex.weight,
+                phrase: Some(ex.phrase.clone()),
+            });
+        }
+        self.samples = samples;
+    }
+
+    fn predict(&mut self, text: &str) -> ClassificationResult {
+        // Unbekannte Tippfehler ergäben zufällige Vektoren
+        let (text, corrections) = self.spelling.correct(text);
+        let input_vec = self.embedder.sentence_vec(&text);
+        ClassificationResult { corrections, ..classify(&input_vec, &self.samples, self.mode) }
+    }
+
+    fn embed(&mut self, text: &str) -> Option<Vec<f32>> {
+        if let Some(v) = self.vectors.get(text) {
+            return Some(v.clone());
+        }
+        let v = self.embedder.sentence_vec(text);
+        self.vectors.insert(text.to_string(), v.clone());
+        Some(v)
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct Snapshot<'a> {
+            mode: ClassifyMode,
+            samples: &'a [IntentSample],
+        }
+
+        serde_json::to_string_pretty(&Snapshot { mode: self.mode, samples: &self.samples })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thresholds::Thresholds;
+
+    fn sample(intent: Intent, vector: Vec<f32>, weight: f32) -> IntentSample {
+        IntentSample { intent, vector, weight, phrase: None }
+    }
+
+    #[test]
+    fn test_ranked_per_intent() {
+        let samples = vec![
+            sample(Intent::Weather, vec![1.0, 0.0], 1.0),
+            sample(Intent::Weather, vec![0.9, 0.1], 1.0),
+            sample(Intent::FileOpen, vec![0.0, 1.0], 1.0),
+        ];
+
+        let result = classify(&[1.0, 0.0], &samples, ClassifyMode::BestSample);
+        assert_eq!(result.candidates.len(), 2);
+        assert_eq!(Thresholds::default().decide(&result), Intent::Weather);
+        assert_eq!(result.best().unwrap().sample_index, Some(0));
+        assert_eq!(result.top(1).len(), 1);
+    }
+
+    #[test]
+    fn test_low_score_is_unknown() {
+        let samples = vec![sample(Intent::Weather, vec![1.0, 0.0], 1.0)];
+        let result = classify(&[0.1, 1.0], &samples, ClassifyMode::BestSample);
+
+        assert!(Thresholds::default().is_uncertain(&result));
+        assert_eq!(Thresholds::default().decide(&result), Intent::Unknown);
+        assert_eq!(result.candidates[0].intent, Intent::Weather);
+    }
 
-        if score > best_score {
-            best_score = score;
-            best_intent = s.intent.clone();
+    #[test]
+    fn test_knn_outvotes_heavy_sample() {
+        // Ein gelerntes Beispiel mit Gewicht 2.0 gewinnt bei BestSample ...
+        let samples = vec![
+            sample(Intent::Search, vec![0.8, 0.6], 2.0),
+            sample(Intent::Weather, vec![1.0, 0.1], 1.0),
+            sample(Intent::Weather, vec![1.0, 0.2], 1.0),
+            sample(Intent::Weather, vec![1.0, 0.0], 1.0),
+        ];
+        let input = [1.0, 0.3];
+
+        let best = classify(&input, &samples, ClassifyMode::BestSample);
+        assert_eq!(best.best().unwrap().intent, Intent::Search);
+
+        // ... aber nicht gegen drei ähnliche Nachbarn
+        for vote in [Vote::Sum, Vote::DistanceWeighted] {
+            let knn = classify(&input, &samples, ClassifyMode::Knn { k: 4, vote });
+            assert_eq!(knn.best().unwrap().intent, Intent::Weather);
         }
     }
 
-    if best_score < 0.5 {
-        println!("→ Unsicher (Score {:.3})\n", best_score);
-        return Intent::Unknown;
+    #[test]
+    fn test_centroid() {
+        let samples = vec![
+            sample(Intent::Weather, vec![1.0, 0.0], 1.0),
+            sample(Intent::Weather, vec![0.0, 1.0], 1.0),
+            sample(Intent::FileOpen, vec![-1.0, 0.0], 1.0),
+        ];
+
+        let result = classify(&[1.0, 1.0], &samples, ClassifyMode::Centroid);
+        let best = result.best().unwrap();
+        assert_eq!(best.intent, Intent::Weather);
+        assert!((best.similarity - 1.0).abs() < 1e-5);
     }
 
-    println!("→ Gewählter Intent: {:?}\n", best_intent);
-    best_intent
+    /// Zählt die eingebetteten Phrasen
+    struct CountingEmbedder(std::rc::Rc<std::cell::Cell<usize>>);
+
+    impl SentenceEmbedder for CountingEmbedder {
+        fn dim(&self) -> usize {
+            2
+        }
+
+        fn sentence_vec(&mut self, text: &str) -> Vec<f32> {
+            self.0.set(self.0.get() + 1);
+            vec![text.len() as f32, 1.0]
+        }
+    }
+
+    #[test]
+    fn test_retrain_embeds_only_new_phrases() {
+        let example = |phrase: &str, weight: f32| TrainingExample {
+            intent: Intent::Weather,
+            phrase: phrase.to_string(),
+            weight,
+        };
+        let data = |examples| TrainingSet { examples, doc_stats: DocStats::new() };
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut classifier = CosineClassifier::new(Box::new(CountingEmbedder(count.clone())), ClassifyMode::BestSample);
+
+        classifier.train(&data(vec![example("wetter", 1.0), example("regen", 1.0)]));
+        assert_eq!(count.get(), 2);
+
+        // Nur Gewichte geändert
+        classifier.train(&data(vec![example("wetter", 2.0), example("regen", 1.0)]));
+        assert_eq!(count.get(), 2);
+        assert_eq!(classifier.samples[0].weight, 2.0);
+
+        classifier.train(&data(vec![example("wetter", 2.0), example("regen", 1.0), example("sonne", 1.0)]));
+        assert_eq!(count.get(), 3);
+    }
 }
@@ -0,0 +1,219 @@
+use crate::config::Config;
+use crate::intent::Intent;
+use crate::learning::{self, LearningStore, SampleSource};
+use crate::samples;
+use crate::similarity::cosine_similarity;
+use crate::vector::SentenceEmbedder;
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+/// Regeln zum Verdichten der gelernten Beispiele (`compaction` in
+/// `config.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Compaction {
+    /// Ab dieser Ähnlichkeit werden Beispiele desselben Intents zusammengeführt
+    pub merge_similarity: f32,
+    /// Ab dieser Ähnlichkeit gelten Beispiele verschiedener Intents als
+    /// Widerspruch
+    pub conflict_similarity: f32,
+    /// Höchstzahl gelernter Beispiele
+    pub max_samples: usize,
+}
+
+impl Default for Compaction {
+    fn default() -> Self {
+        Self { merge_similarity: 0.95, conflict_similarity: 0.95, max_samples: 500 }
+    }
+}
+
+/// Zwei fast gleiche Beispiele mit verschiedenen Intents
+pub struct Conflict {
+    pub phrases: [String; 2],
+    pub intents: [Intent; 2],
+    pub similarity: f32,
+}
+
+#[derive(Default)]
+pub struct CompactReport {
+    pub before: usize,
+    pub merged: usize,
+    pub evicted: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl fmt::Display for CompactReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Beispiele vorher:  {}", self.before)?;
+        writeln!(f, "Zusammengeführt:   {}", self.merged)?;
+        writeln!(f, "Verdrängt:         {}", self.evicted)?;
+        writeln!(f, "Beispiele nachher: {}", self.before - self.merged - self.evicted)?;
+
+        if !self.conflicts.is_empty() {
+            writeln!(f, "\nWidersprüche (bitte prüfen):")?;
+            for c in &self.conflicts {
+                writeln!(
+                    f,
+                    "  \"{}\" ({}) ↔ \"{}\" ({}): {:.3}",
+                    c.phrases[0], c.intents[0].label(), c.phrases[1], c.intents[1].label(), c.similarity
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Führt fast gleiche Beispiele desselben Intents zusammen, meldet
+/// Widersprüche und hält die Höchstzahl ein. Das ältere Beispiel bleibt
+/// erhalten und übernimmt Treffer und Fehlschläge des jüngeren.
+pub fn compact(store: &mut LearningStore, embedder: &mut dyn SentenceEmbedder, rules: &Compaction) -> CompactReport {
+    let mut report = CompactReport { before: store.samples.len(), ..Default::default() };
+    embedder.set_doc_stats(samples::training_set(store).doc_stats);
+
+    let vectors: Vec<Vec<f32>> = store.samples.iter().map(|s| embedder.sentence_vec(&s.phrase)).collect();
+    let mut kept: Vec<usize> = Vec::new();
+    let mut removed = vec![false; store.samples.len()];
+
+    for i in 0..store.samples.len() {
+        let mut merged_into = None;
+        for &k in &kept {
+            let sim = cosine_similarity(&vectors[i], &vectors[k]);
+            let (a, b) = (&store.samples[k], &store.samples[i]);
+            if a.intent == b.intent {
+                if sim >= rules.merge_similarity && merged_into.is_none() {
+                    merged_into = Some(k);
+                }
+            } else if sim >= rules.conflict_similarity {
+                report.conflicts.push(Conflict {
+                    phrases: [a.phrase.clone(), b.phrase.clone()],
+                    intents: [a.intent.clone(), b.intent.clone()],
+                    similarity: sim,
+                });
+            }
+        }
+
+        match merged_into {
+            Some(k) => {
+                let dup = store.samples[i].clone();
+                let target = &mut store.samples[k];
+                target.hits += dup.hits;
+                target.misses += dup.misses;
+                target.weight = target.weight.max(dup.weight);
+                target.last_used = target.last_used.max(dup.last_used);
+                if dup.source == SampleSource::Taught {
+                    target.source = SampleSource::Taught;
+                }
+                store.doc_stats.remove_document(&dup.phrase);
+                removed[i] = true;
+                report.merged += 1;
+            }
+            None => kept.push(i),
+        }
+    }
+
+    let mut removed = removed.into_iter();
+    store.samples.retain(|_| !removed.next().unwrap_or(false));
+    report.evicted = enforce_limit(store, rules.max_samples);
+    report
+}
+
+/// Verdrängt die am wenigsten nützlichen Beispiele, bis höchstens `max`
+/// übrig sind: nach Gewicht, bei Gleichstand das ältere. Gibt die Anzahl
+/// verdrängter Beispiele zurück.
+pub fn enforce_limit(store: &mut LearningStore, max: usize) -> usize {
+    let total = store.samples.len();
+    let excess = total.saturating_sub(max);
+
+    if excess > 0 {
+        let mut order: Vec<usize> = (0..store.samples.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&store.samples[a], &store.samples[b]);
+            a.weight.total_cmp(&b.weight).then(a.created.cmp(&b.created))
+        });
+
+        let mut evict = vec![false; store.samples.len()];
+        for &i in &order[..excess] {
+            evict[i] = true;
+            store.doc_stats.remove_document(&store.samples[i].phrase);
+        }
+        let mut evict = evict.into_iter();
+        store.samples.retain(|_| !evict.next().unwrap_or(false));
+    }
+
+    total - store.samples.len()
+}
+
+/// Kommandozeile: `compact [--dry-run]`
+pub fn run(args: &[String]) {
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+
+    let config = Config::load();
+    let mut learning = LearningStore::load();
+    let mut embedder = config.build_embedder();
+    // Verdrängt wird nach dem aktuellen Gewicht
+    learning.decay(&config.reinforcement, learning::now());
+
+    let report = compact(&mut learning, embedder.as_mut(), &config.compaction);
+    println!("{}", report);
+
+    if dry_run {
+        println!("Probelauf, nichts geschrieben.");
+    } else {
+        learning.save();
+        println!("Gelernte Beispiele gespeichert.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learning::LearnedSample;
+    use crate::vector::Vocab;
+
+    fn store(samples: &[(Intent, &str)]) -> LearningStore {
+        let mut store = LearningStore::new();
+        for (intent, phrase) in samples {
+            store.doc_stats.add_document(phrase);
+            store.samples.push(LearnedSample::taught(intent.clone(), phrase));
+        }
+        store
+    }
+
+    #[test]
+    fn test_merge_and_conflicts() {
+        let mut store = store(&[
+            (Intent::Weather, "wie siehts draußen aus"),
+            (Intent::Search, "recherchier mal"),
+            (Intent::Weather, "Wie sieht's draußen aus?"),
+            (Intent::FileOpen, "recherchier mal!"),
+        ]);
+        store.samples[2].hits = 3;
+
+        let report = compact(&mut store, &mut Vocab::new(32), &Compaction::default());
+
+        assert_eq!(report.merged, 1);
+        assert_eq!(store.samples.len(), 3);
+        assert_eq!(store.samples[0].phrase, "wie siehts draußen aus");
+        assert_eq!(store.samples[0].hits, 3);
+        assert_eq!(store.doc_stats.docs, 3);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].intents, [Intent::Search, Intent::FileOpen]);
+    }
+
+    #[test]
+    fn test_evicts_least_useful() {
+        let mut store = store(&[
+            (Intent::Weather, "wie siehts draußen aus"),
+            (Intent::Search, "recherchier mal"),
+            (Intent::FileOpen, "mach den editor auf"),
+        ]);
+        store.samples[0].weight = 2.5;
+        store.samples[1].weight = 0.5;
+
+        assert_eq!(enforce_limit(&mut store, 2), 1);
+        let phrases: Vec<&str> = store.samples.iter().map(|s| s.phrase.as_str()).collect();
+        assert_eq!(phrases, ["wie siehts draußen aus", "mach den editor auf"]);
+        assert_eq!(store.doc_stats.df.get(&crate::normalize::normalize("recherchier")), None);
+        assert_eq!(enforce_limit(&mut store, 5), 0);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::classifier::{Classifier, ClassifyMode, ClassifierKind, CosineClassifier};
+use crate::compact::Compaction;
+use crate::bayes::NaiveBayes;
+use crate::embeddings::Embeddings;
+use crate::learning::Reinforcement;
+use crate::synonyms::Thesaurus;
+use crate::transformer::SentenceEncoder;
+use crate::vector::{self, SentenceEmbedder, Vocab};
+use serde::{Serialize, Deserialize};
+use std::fs;
+
+const FILE: &str = "config.json";
+
+/// fastText-Dateien sind nach Häufigkeit sortiert, die ersten 200.000
+/// Wörter decken fast alles ab
+const EMBEDDINGS_LIMIT: usize = 200_000;
+
+/// Dimension der gehashten n-Gramm-Vektoren
+const DIM: usize = 32;
+
+/// Laufzeit-Konfiguration aus `config.json`. Fehlende Felder nutzen die
+/// Standardwerte, eine fehlende Datei ergibt die Standardkonfiguration.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub classifier: ClassifierKind,
+    /// Nur für `ClassifierKind::Cosine`
+    pub classify_mode: ClassifyMode,
+    /// Schreibt das trainierte Modell als JSON in diese Datei (zum Vergleichen)
+    pub model_dump: Option<String>,
+    /// Zusätzliche Synonyme im OpenThesaurus-Textformat (ergänzt
+    /// `data/synonyms.txt`)
+    pub synonyms: Option<String>,
+    /// Dimension der gehashten n-Gramm-Vektoren (Standard 32)
+    pub dim: Option<usize>,
+    /// Kürzeste und längste Zeichen-n-Gramme (Standard 3 und 5)
+    pub min_n: Option<usize>,
+    pub max_n: Option<usize>,
+    /// Vortrainierte Wortvektoren (fastText/word2vec `.vec`) statt der
+    /// gehashten n-Gramm-Vektoren
+    pub embeddings: Option<String>,
+    /// Höchstens so viele Zeilen der Vektordatei indizieren
+    pub embeddings_limit: Option<usize>,
+    /// Verzeichnis mit `model.onnx` und `tokenizer.json` eines Satz-
+    /// Transformers (z.B. paraphrase-multilingual-MiniLM-L12-v2)
+    pub transformer: Option<String>,
+    /// Regeln für das Lernen aus Erfolg und Widerspruch
+    pub reinforcement: Reinforcement,
+    /// Zusammenführen ähnlicher und Begrenzen gelernter Beispiele
+    pub compaction: Compaction,
+}
+
+impl Config {
+    pub fn load() -> Self {
+        match fs::read_to_string(FILE) {
+            Ok(data) => match serde_json::from_str(&data) {
+                Ok(config) => {
+                    println!("Konfiguration geladen.");
+                    config
+                }
+                Err(e) => {
+                    eprintln!("Fehler in {}: {} – nutze Standardwerte.", FILE, e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Erstellt den konfigurierten (noch untrainierten) Klassifizierer
+    pub fn build_classifier(&self) -> Box<dyn Classifier> {
+        match self.classifier {
+            ClassifierKind::Cosine => {
+                Box::new(CosineClassifier::new(self.build_embedder(), self.classify_mode))
+            }
+            ClassifierKind::NaiveBayes => Box::new(NaiveBayes::new()),
+        }
+    }
+
+    /// Transformer, wenn konfiguriert und ladbar, sonst Wortvektoren
+    pub fn build_embedder(&self) -> Box<dyn SentenceEmbedder> {
+        if let Some(dir) = &self.transformer {
+            match SentenceEncoder::load(dir) {
+                Ok(encoder) => {
+                    println!("Transformer geladen: Dimension {}.", encoder.dim());
+                    return Box::new(encoder);
+                }
+                Err(e) => eprintln!("{} – nutze Wortvektoren.", e),
+            }
+        }
+
+        let vocab = Vocab::new(self.dim.unwrap_or(DIM))
+            .with_ngrams(self.min_n.unwrap_or(vector::MIN_N), self.max_n.unwrap_or(vector::MAX_N))
+            .with_thesaurus(self.thesaurus());
+        match self.load_embeddings() {
+            Some(embeddings) => Box::new(vocab.with_embeddings(embeddings)),
+            None => Box::new(vocab),
+        }
+    }
+
+    fn load_embeddings(&self) -> Option<Embeddings> {
+        let path = self.embeddings.as_ref()?;
+        match Embeddings::open(path, self.embeddings_limit.unwrap_or(EMBEDDINGS_LIMIT)) {
+            Ok(embeddings) => {
+                println!("Wortvektoren geladen: {} Wörter, Dimension {}.", embeddings.len(), embeddings.dim());
+                Some(embeddings)
+            }
+            Err(e) => {
+                eprintln!("{} – nutze gehashte Vektoren.", e);
+                None
+            }
+        }
+    }
+
+    fn thesaurus(&self) -> Thesaurus {
+        let mut thesaurus = Thesaurus::bundled();
+        if let Some(path) = &self.synonyms {
+            if let Err(e) = thesaurus.load_file(path) {
+                eprintln!("{}", e);
+            }
+        }
+        thesaurus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashing_from_config() {
+        assert_eq!(Config::default().build_embedder().dim(), DIM);
+
+        let config: Config = serde_json::from_str(r#"{"dim": 64, "min_n": 2, "max_n": 4}"#).unwrap();
+        assert_eq!(config.build_embedder().dim(), 64);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::normalize::normalize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+/// Vortrainierte Wortvektoren im Textformat von fastText/word2vec
+/// (`.vec`): optionale Kopfzeile "Anzahl Dimension", dann pro Zeile ein
+/// Wort und seine Werte, durch Leerzeichen getrennt.
+///
+/// Die Datei wird beim Öffnen nur einmal durchlaufen und pro Wort der
+/// Zeilenanfang gemerkt; Vektoren werden erst bei Bedarf gelesen. Der Index
+/// nutzt die normalisierte Form (wie `Vocab` die Wörter sieht), bei
+/// mehreren Wörtern mit gleicher Form gewinnt das erste – in fastText-
+/// Dateien das häufigste.
+pub struct Embeddings {
+    reader: BufReader<File>,
+    index: HashMap<String, u64>,
+    dim: usize,
+}
+
+impl Embeddings {
+    /// Öffnet die Datei und indiziert höchstens `limit` Zeilen
+    pub fn open(path: &str, limit: usize) -> Result<Self, String> {
+        let error = |e: std::io::Error| format!("Wortvektoren '{}': {}", path, e);
+        let mut reader = BufReader::new(File::open(path).map_err(error)?);
+
+        let mut index = HashMap::new();
+        let mut dim = None;
+        let mut offset = 0u64;
+        let mut line = String::new();
+        let mut rows = 0;
+
+        for n in 0.. {
+            if rows >= limit {
+                break;
+            }
+            line.clear();
+            let read = reader.read_line(&mut line).map_err(error)?;
+            if read == 0 {
+                break;
+            }
+            let start = offset;
+            offset += read as u64;
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                // Kopfzeile "2000000 300"
+                [_, d] if n == 0 && d.parse::<usize>().is_ok() => {
+                    dim = d.parse().ok();
+                    continue;
+                }
+                [word, values @ ..] if !values.is_empty() => {
+                    rows += 1;
+                    let d = *dim.get_or_insert(values.len());
+                    let key = normalize(word);
+                    if values.len() == d && !key.is_empty() && !key.contains(' ') {
+                        index.entry(key).or_insert(start);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match dim {
+            Some(dim) if dim > 0 => Ok(Self { reader, index, dim }),
+            _ => Err(format!("Wortvektoren '{}': keine Vektoren gefunden", path)),
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Vektor eines normalisierten Wortes, auf Länge 1 skaliert
+    pub fn get(&mut self, word: &str) -> Option<Vec<f32>> {
+        let offset = *self.index.get(word)?;
+        let mut line = String::new();
+        self.reader.seek(SeekFrom::Start(offset)).ok()?;
+        self.reader.read_line(&mut line).ok()?;
+
+        let mut v: Vec<f32> = line
+            .split_whitespace()
+            .skip(1)
+            .map(|x| x.parse().ok())
+            .collect::<Option<_>>()?;
+        if v.len() != self.dim {
+            return None;
+        }
+
+        unit(&mut v);
+        Some(v)
+    }
+}
+
+/// Skaliert einen Vektor auf Länge 1 (Nullvektor bleibt unverändert)
+pub fn unit(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Schreibt eine kleine `.vec`-Datei ins Temp-Verzeichnis, der Test
+    /// löscht sie am Ende
+    fn write_vec_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("assistent-{}-{}.vec", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_lazy_lookup() {
+        let path = write_vec_file("lookup", "3 2\nWetter 3 4\nwetter 1 0\nDatei 0 -2\n");
+        let mut emb = Embeddings::open(&path, 100).unwrap();
+
+        assert_eq!(emb.dim(), 2);
+        assert_eq!(emb.len(), 2);
+        // Erste Zeile mit gleicher normalisierter Form gewinnt
+        assert_eq!(emb.get(&normalize("wetter")), Some(vec![0.6, 0.8]));
+        assert_eq!(emb.get(&normalize("Datei")), Some(vec![0.0, -1.0]));
+        assert_eq!(emb.get("unbekannt"), None);
+
+        drop(emb);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_without_header_and_limit() {
+        let path = write_vec_file("noheader", "haus 1 2 3\nbaum 4 5 6\n");
+        let emb = Embeddings::open(&path, 1).unwrap();
+        assert_eq!(emb.dim(), 3);
+        assert_eq!(emb.len(), 1);
+
+        let empty = write_vec_file("empty", "");
+        assert!(Embeddings::open(&empty, 10).is_err());
+        assert!(Embeddings::open("gibt/es/nicht.vec", 10).is_err());
+
+        drop(emb);
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(empty).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+use crate::intent::Intent;
+use crate::classifier::{Classifier, ClassificationResult};
+use crate::boost::{self, BoostRules};
+use crate::config::Config;
+use crate::learning::{LearnedSample, LearningStore};
+use crate::samples;
+use crate::thresholds::Thresholds;
+use crate::vector::DocStats;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+
+pub const DEFAULT_CORPUS: &str = "data/eval_corpus.json";
+
+/// Eine gelabelte Äußerung. `Unknown` markiert Eingaben, die abgelehnt
+/// werden sollen.
+#[derive(Clone, Deserialize)]
+pub struct LabeledUtterance {
+    pub text: String,
+    pub intent: Intent,
+}
+
+pub fn load_corpus(path: &str) -> Result<Vec<LabeledUtterance>, String> {
+    let data = fs::read_to_string(path).map_err(|e| format!("{} nicht lesbar: {}", path, e))?;
+    serde_json::from_str(&data).map_err(|e| format!("{} ist kein gültiger Korpus: {}", path, e))
+}
+
+/// Ergebnis der Pipeline für eine Äußerung
+pub struct Prediction {
+    pub text: String,
+    pub expected: Intent,
+    pub predicted: Intent,
+    pub result: ClassificationResult,
+}
+
+impl Prediction {
+    pub fn is_correct(&self) -> bool {
+        self.expected == self.predicted
+    }
+
+    /// Score des gewählten Kandidaten (auch wenn unter der Schwelle)
+    pub fn score(&self) -> f32 {
+        self.result.best().map_or(0.0, |b| b.score)
+    }
+}
+
+#[derive(Default)]
+pub struct EvalReport {
+    pub predictions: Vec<Prediction>,
+}
+
+impl EvalReport {
+    pub fn accuracy(&self) -> f32 {
+        if self.predictions.is_empty() {
+            return 0.0;
+        }
+        let correct = self.predictions.iter().filter(|p| p.is_correct()).count();
+        correct as f32 / self.predictions.len() as f32
+    }
+
+    pub fn count(&self, expected: &Intent, predicted: &Intent) -> usize {
+        self.predictions
+            .iter()
+            .filter(|p| &p.expected == expected && &p.predicted == predicted)
+            .count()
+    }
+
+    /// (Precision, Recall, Anzahl erwartet)
+    pub fn precision_recall(&self, intent: &Intent) -> (f32, f32, usize) {
+        let tp = self.count(intent, intent);
+        let predicted = self.predictions.iter().filter(|p| &p.predicted == intent).count();
+        let expected = self.predictions.iter().filter(|p| &p.expected == intent).count();
+
+        let ratio = |a: usize, b: usize| if b == 0 { 0.0 } else { a as f32 / b as f32 };
+        (ratio(tp, predicted), ratio(tp, expected), expected)
+    }
+
+    /// Falsch klassifizierte Äußerungen, die sicherste Fehlentscheidung zuerst
+    pub fn worst(&self, n: usize) -> Vec<&Prediction> {
+        let mut wrong: Vec<&Prediction> = self.predictions.iter().filter(|p| !p.is_correct()).collect();
+        wrong.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        wrong.truncate(n);
+        wrong
+    }
+
+    pub fn merge(&mut self, other: EvalReport) {
+        self.predictions.extend(other.predictions);
+    }
+
+    fn labels() -> impl Iterator<Item = Intent> {
+        Intent::ALL.into_iter().chain(std::iter::once(Intent::Unknown))
+    }
+}
+
+impl fmt::Display for EvalReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let correct = self.predictions.iter().filter(|p| p.is_correct()).count();
+        writeln!(f, "Genauigkeit: {:.1}% ({}/{})", self.accuracy() * 100.0, correct, self.predictions.len())?;
+
+        writeln!(f, "\n{:<10} {:>9} {:>9} {:>6}", "Intent", "Precision", "Recall", "n")?;
+        for intent in Self::labels() {
+            let (p, r, n) = self.precision_recall(&intent);
+            writeln!(f, "{:<10} {:>9.2} {:>9.2} {:>6}", short(&intent), p, r, n)?;
+        }
+
+        writeln!(f, "\nKonfusionsmatrix (Zeile = erwartet, Spalte = erkannt)")?;
+        write!(f, "{:<10}", "")?;
+        for intent in Self::labels() {
+            write!(f, " {:>8}", short(&intent))?;
+        }
+        writeln!(f)?;
+        for expected in Self::labels() {
+            write!(f, "{:<10}", short(&expected))?;
+            for predicted in Self::labels() {
+                write!(f, " {:>8}", self.count(&expected, &predicted))?;
+            }
+            writeln!(f)?;
+        }
+
+        let worst = self.worst(10);
+        if !worst.is_empty() {
+            writeln!(f, "\nSchlimmste Fehler:")?;
+            for p in worst {
+                writeln!(f, "  {:.3}  {:?} statt {:?}: \"{}\"", p.score(), p.predicted, p.expected, p.text)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn short(intent: &Intent) -> String {
+    format!("{:?}", intent).chars().take(8).collect()
+}
+
+/// Führt die komplette Pipeline (Klassifizierer + Boost + Schwelle) aus
+pub fn evaluate(
+    classifier: &mut dyn Classifier,
+    rules: &BoostRules,
+    thresholds: &Thresholds,
+    corpus: &[LabeledUtterance],
+) -> EvalReport {
+    let predictions = corpus
+        .iter()
+        .map(|u| {
+            let mut result = classifier.predict(&u.text);
+            rules.apply(&mut result, &u.text);
+            Prediction {
+                text: u.text.clone(),
+                expected: u.intent.clone(),
+                predicted: thresholds.decide(&result),
+                result,
+            }
+        })
+        .collect();
+
+    EvalReport { predictions }
+}
+
+/// k-fache Kreuzvalidierung über die gelernten Beispiele:
+/// trainiert jeweils auf den statischen Beispielen plus k-1 Teilen und
+/// testet auf dem verbleibenden Teil.
+pub fn cross_validate(
+    make: &dyn Fn() -> Box<dyn Classifier>,
+    rules: &BoostRules,
+    thresholds: &Thresholds,
+    learned: &[LearnedSample],
+    k: usize,
+) -> Option<EvalReport> {
+    if k < 2 || learned.len() < k {
+        return None;
+    }
+
+    let mut report = EvalReport::default();
+    for fold in 0..k {
+        let mut train = Vec::new();
+        let mut test = Vec::new();
+        let mut stats = DocStats::new();
+
+        for (i, s) in learned.iter().enumerate() {
+            if i % k == fold {
+                test.push(LabeledUtterance { text: s.phrase.clone(), intent: s.intent.clone() });
+            } else {
+                stats.add_document(&s.phrase);
+                train.push(s.clone());
+            }
+        }
+
+        let mut classifier = make();
+        classifier.train(&samples::training_set_from(&train, &stats));
+        report.merge(evaluate(classifier.as_mut(), rules, thresholds, &test));
+    }
+
+    Some(report)
+}
+
+/// Trainierter Klassifizierer, Boost-Regeln und Korpus für die Kommandozeile
+pub struct Pipeline {
+    pub classifier: Box<dyn Classifier>,
+    pub rules: BoostRules,
+    pub corpus: Vec<LabeledUtterance>,
+}
+
+pub fn load_pipeline(config: &Config, learning: &LearningStore, corpus_path: &str) -> Result<Pipeline, String> {
+    let rules = BoostRules::load(boost::FILE).map_err(|e| e.to_string())?;
+    let corpus = load_corpus(corpus_path)?;
+
+    let mut classifier = config.build_classifier();
+    classifier.train(&samples::training_set(learning));
+    Ok(Pipeline { classifier, rules, corpus })
+}
+
+/// Kommandozeile: `eval [korpus.json] [--folds k]`
+pub fn run(args: &[String]) {
+    let mut corpus_path = DEFAULT_CORPUS.to_string();
+    let mut folds = 5;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--folds" {
+            match iter.next().and_then(|n| n.parse().ok()) {
+                Some(n) => folds = n,
+                None => {
+                    eprintln!("--folds erwartet eine Zahl");
+                    return;
+                }
+            }
+        } else {
+            corpus_path = arg.clone();
+        }
+    }
+
+    let config = Config::load();
+    let learning = LearningStore::load();
+    let thresholds = Thresholds::load();
+    let Pipeline { mut classifier, rules, corpus } = match load_pipeline(&config, &learning, &corpus_path) {
+        Ok(pipeline) => pipeline,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    println!("=== {} auf {} ({} Äußerungen) ===\n", classifier.name(), corpus_path, corpus.len());
+    println!("{}", evaluate(classifier.as_mut(), &rules, &thresholds, &corpus));
+
+    let make = || config.build_classifier();
+    match cross_validate(&make, &rules, &thresholds, &learning.samples, folds) {
+        Some(report) => {
+            println!("=== {}-fache Kreuzvalidierung über gelernte Beispiele ===\n", folds);
+            println!("{}", report);
+        }
+        None => println!("Zu wenige gelernte Beispiele für eine {}-fache Kreuzvalidierung.", folds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bayes::NaiveBayes;
+
+    fn utterance(text: &str, intent: Intent) -> LabeledUtterance {
+        LabeledUtterance { text: text.to_string(), intent }
+    }
+
+    #[test]
+    fn test_report_metrics() {
+        let mut classifier = NaiveBayes::new();
+        classifier.train(&samples::training_set(&LearningStore::new()));
+
+        let corpus = vec![
+            utterance("temperatur und wetter", Intent::Weather),
+            utterance("tschüss", Intent::Goodbye),
+            utterance("tschüss", Intent::Weather),
+        ];
+        let report = evaluate(&mut classifier, &BoostRules::default(), &Thresholds::default(), &corpus);
+
+        assert!((report.accuracy() - 2.0 / 3.0).abs() < 1e-5);
+        assert_eq!(report.count(&Intent::Weather, &Intent::Goodbye), 1);
+        assert_eq!(report.precision_recall(&Intent::Weather), (1.0, 0.5, 2));
+        assert_eq!(report.worst(5)[0].text, "tschüss");
+    }
+
+    #[test]
+    fn test_bundled_corpus_parses() {
+        let corpus = load_corpus(DEFAULT_CORPUS).unwrap();
+        assert!(corpus.len() > 30);
+    }
+}
@@ -1,7 +1,13 @@
 use eframe::egui;
+use crate::boost::{self, BoostRules, MatchText};
+use crate::intent::Intent;
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc::{Sender, Receiver};
 
+/// Backend-Nachrichten mit diesem Anfang bestätigen Gelerntes und werden
+/// hervorgehoben
+pub const LEARNED_PREFIX: &str = "Gelernt: ";
+
 pub struct AssistantApp {
     input: String,
     chat_history: Vec<ChatMessage>,
@@ -9,7 +15,8 @@ pub struct AssistantApp {
     is_minimized: bool,
     tx: Sender<String>,
     rx: Arc<Mutex<Receiver<String>>>,
-    backend_tx: Sender<String>,
+    /// Für die Abschiedserkennung; Fehler meldet das Backend
+    boost_rules: BoostRules,
 }
 
 #[derive(Clone)]
@@ -19,7 +26,7 @@ pub struct ChatMessage {
 }
 
 impl AssistantApp {
-    pub fn new(tx: Sender<String>, rx: Receiver<String>, backend_tx: Sender<String>) -> Self {
+    pub fn new(tx: Sender<String>, rx: Receiver<String>) -> Self {
         Self {
             input: String::new(),
             chat_history: vec![
@@ -32,7 +39,7 @@ impl AssistantApp {
             is_minimized: false,
             tx,
             rx: Arc::new(Mutex::new(rx)),
-            backend_tx,
+            boost_rules: BoostRules::load(boost::FILE).unwrap_or_default(),
         }
     }
 
@@ -51,12 +58,13 @@ impl AssistantApp {
         let message = self.input.clone();
         self.add_message(true, &message);
         
-        let lower = message.to_lowercase();
-        if !self.is_active && (lower.contains("hallo") || lower.contains("hey") || lower.contains("hi")) {
+        let text = MatchText::new(&message);
+        if !self.is_active && text.contains_any(&["hallo", "hey", "hi"]) {
             self.is_active = true;
         }
         
-        if self.is_active && (lower.contains("danke") || lower.contains("tschüss") || lower.contains("bis")) {
+        let _ = self.boost_rules.reload_if_changed();
+        if self.is_active && self.boost_rules.signals(&Intent::Goodbye, &text) {
             self.is_minimized = true;
             self.add_message(false, "Fenster wird minimiert. Sage 'Hallo' zum Reaktivieren.");
         }
@@ -191,9 +199,16 @@ impl eframe::App for AssistantApp {
                                             .color(egui::Color32::from_rgb(150, 150, 200))
                                     );
                                     
-                                    let text = egui::RichText::new(&msg.text)
-                                        .size(15.0)
-                                        .color(egui::Color32::from_rgb(220, 220, 230));
+                                    // Bestätigung einer Korrektur (grün)
+                                    let text = if msg.text.starts_with(LEARNED_PREFIX) {
+                                        egui::RichText::new(format!("✓ {}", msg.text))
+                                            .size(15.0)
+                                            .color(egui::Color32::from_rgb(100, 255, 150))
+                                    } else {
+                                        egui::RichText::new(&msg.text)
+                                            .size(15.0)
+                                            .color(egui::Color32::from_rgb(220, 220, 230))
+                                    };
                                     
                                     ui.add(
                                         egui::Label::new(text)
@@ -260,7 +275,6 @@ impl eframe::App for AssistantApp {
 pub fn create_window(
     tx: Sender<String>,
     rx: Receiver<String>,
-    backend_tx: Sender<String>,
 ) -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -274,6 +288,6 @@ pub fn create_window(
     eframe::run_native(
         "KI-Assistent",
         options,
-        Box::new(|_cc| Ok(Box::new(AssistantApp::new(tx, rx, backend_tx)))),
+        Box::new(|_cc| Ok(Box::new(AssistantApp::new(tx, rx)))),
     )
 }
\ No newline at end of file
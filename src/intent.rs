@@ -1,13 +1,98 @@
+use crate::parser::slots::{SlotKind, SlotSpec};
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+const WEATHER_SLOTS: &[SlotSpec] = &[
+    SlotSpec::required(SlotKind::Location, "Für welchen Ort?"),
+    // Tag bzw. Zeitraum ("die nächsten 3 Tage") der Vorhersage
+    SlotSpec::optional(SlotKind::DateTime),
+    // Zahlen wie "bei 25 Grad"; die Anzahl Tage kommt nur aus dem Zeitraum
+    SlotSpec::optional(SlotKind::Number),
+];
+const SEARCH_SLOTS: &[SlotSpec] = &[SlotSpec::required(SlotKind::Query, "Wonach soll ich suchen?")];
+const FILE_OPEN_SLOTS: &[SlotSpec] = &[
+    SlotSpec::optional(SlotKind::Application),
+    SlotSpec::required(SlotKind::Path, "Welche Datei soll ich öffnen?").or(SlotKind::Application),
+];
+const FILE_SHOW_SLOTS: &[SlotSpec] = &[SlotSpec::required(SlotKind::Path, "Was soll ich anzeigen?")];
+const CONVERT_SLOTS: &[SlotSpec] = &[
+    SlotSpec::required(SlotKind::Number, "Was soll ich umrechnen?"),
+    SlotSpec::required(SlotKind::Unit, "In welche Einheit?"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Intent {
     Greeting,
     Weather,
     Search,
     FileOpen,      // Neue Intents
     FileShow,
+    Convert,       // Einheiten umrechnen
     Goodbye,       // Fenster schließen, aber weiter laufen
     Shutdown,      // Komplett beenden
     Unknown,
-}
\ No newline at end of file
+}
+
+impl Intent {
+    /// Alle erkennbaren Intents (ohne `Unknown`)
+    pub const ALL: [Intent; 8] = [
+        Intent::Greeting,
+        Intent::Weather,
+        Intent::Search,
+        Intent::FileOpen,
+        Intent::FileShow,
+        Intent::Convert,
+        Intent::Goodbye,
+        Intent::Shutdown,
+    ];
+
+    /// Anzeigename für Rückfragen in der GUI
+    pub fn label(&self) -> &'static str {
+        match self {
+            Intent::Greeting => "Begrüßung",
+            Intent::Weather => "Wetter",
+            Intent::Search => "Suche",
+            Intent::FileOpen => "Datei öffnen",
+            Intent::FileShow => "Datei anzeigen",
+            Intent::Convert => "Umrechnung",
+            Intent::Goodbye => "Verabschiedung",
+            Intent::Shutdown => "Beenden",
+            Intent::Unknown => "Unbekannt",
+        }
+    }
+
+    /// Intent zu einem Namen, wie ihn Nutzer in Korrekturen verwenden
+    /// ("Wetter", "die Suche", "Datei öffnen"), ohne Artikel
+    pub fn from_name(name: &str) -> Option<Intent> {
+        let name = name.trim().trim_end_matches(['.', '!', '?']).to_lowercase();
+        let name = ["der ", "die ", "das ", "den ", "eine ", "einen ", "ein "]
+            .iter()
+            .find_map(|article| name.strip_prefix(article))
+            .unwrap_or(&name)
+            .trim();
+
+        let intent = match name {
+            "begrüßung" | "gruß" | "hallo" => Intent::Greeting,
+            "wetter" | "wettervorhersage" => Intent::Weather,
+            "suche" | "suchen" | "websuche" | "wikipedia" => Intent::Search,
+            "datei öffnen" | "öffnen" | "programm starten" | "starten" => Intent::FileOpen,
+            "datei anzeigen" | "anzeigen" | "zeigen" => Intent::FileShow,
+            "umrechnung" | "umrechnen" | "einheiten umrechnen" => Intent::Convert,
+            "verabschiedung" | "tschüss" | "abschied" => Intent::Goodbye,
+            "beenden" | "ausschalten" | "herunterfahren" => Intent::Shutdown,
+            _ => return None,
+        };
+        Some(intent)
+    }
+
+    /// Slots, die der Intent aus der Äußerung braucht
+    pub fn slots(&self) -> &'static [SlotSpec] {
+        match self {
+            Intent::Weather => WEATHER_SLOTS,
+            Intent::Search => SEARCH_SLOTS,
+            Intent::FileOpen => FILE_OPEN_SLOTS,
+            Intent::FileShow => FILE_SHOW_SLOTS,
+            Intent::Convert => CONVERT_SLOTS,
+            _ => &[],
+        }
+    }
+}
@@ -1,29 +1,279 @@
 use crate::intent::Intent;
 use crate::classifier::IntentSample;
-use crate::vector::Vocab;
+use crate::compact::Compaction;
+use crate::normalize::normalize;
+use crate::vector::DocStats;
 use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
 use std::fs;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const FILE: &str = "learned_samples.json";
 
+/// Aktuelles Dateiformat. Version 1 (ohne Feld) speicherte nur Vektoren.
+const VERSION: u32 = 2;
+
+/// Gewicht eines neu beigebrachten Beispiels
+const INITIAL_WEIGHT: f32 = 2.0;
+
+/// Kleinere Änderungen durch den Zerfall werden nicht übernommen, damit
+/// nicht bei jeder Nachricht neu trainiert und gespeichert wird
+const DECAY_STEP: f32 = 0.01;
+
+const SECS_PER_DAY: f32 = 86_400.0;
+
+/// Wie ein Beispiel in den Speicher kam
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SampleSource {
+    /// Vom Nutzer beigebracht
+    #[default]
+    Taught,
+    /// Aus dem alten Dateiformat übernommen
+    Migrated,
+}
+
+/// Ein gelerntes Beispiel. Gespeichert wird die Phrase; den Vektor
+/// berechnet der Klassifizierer beim Training mit dem aktiven Verfahren neu,
+/// Änderungen an `Vocab`, `normalize` oder der Dimension entwerten so
+/// nichts Gelerntes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LearnedSample {
+    pub intent: Intent,
+    pub phrase: String,
+    pub weight: f32,
+    /// Ausgangsgewicht vor Treffern, Fehlschlägen und Zerfall: 2.0 für
+    /// Beigebrachtes, das alte Gewicht für Übernommenes
+    #[serde(default = "initial_weight")]
+    pub base: f32,
+    /// Zeitpunkt des Lernens (Unix-Sekunden, 0 = unbekannt)
+    #[serde(default)]
+    pub created: u64,
+    #[serde(default)]
+    pub source: SampleSource,
+    /// Ausgeführte Aktionen ohne Widerspruch
+    #[serde(default)]
+    pub hits: u32,
+    /// Korrekturen und Umformulierungen direkt danach
+    #[serde(default)]
+    pub misses: u32,
+    /// Letzte Bestätigung durch eine erfolgreiche Aktion (Unix-Sekunden,
+    /// 0 = nie)
+    #[serde(default)]
+    pub last_used: u64,
+}
+
+impl LearnedSample {
+    /// Neu beigebrachtes Beispiel
+    pub fn taught(intent: Intent, phrase: &str) -> Self {
+        Self {
+            intent,
+            phrase: phrase.to_string(),
+            weight: INITIAL_WEIGHT,
+            base: INITIAL_WEIGHT,
+            created: now(),
+            source: SampleSource::Taught,
+            hits: 0,
+            misses: 0,
+            last_used: 0,
+        }
+    }
+
+    /// Ab hier zerfällt das Gewicht (0 = unbekannt, kein Zerfall)
+    fn last_active(&self) -> u64 {
+        self.last_used.max(self.created)
+    }
+}
+
+fn initial_weight() -> f32 {
+    INITIAL_WEIGHT
+}
+
+/// Ergebnis von `LearningStore::add_phrase`
+#[derive(Default)]
+pub struct Learned {
+    /// Index des vorhandenen Beispiels, das statt eines neuen aufgefrischt wurde
+    pub merged_into: Option<usize>,
+    /// Entfernte Beispiele mit anderem Intent
+    pub replaced: Vec<LearnedSample>,
+}
+
+/// Bewertung einer ausgeführten Aktion aus dem Verhalten des Nutzers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Hit,
+    Miss,
+}
+
+/// Regeln für implizites Feedback (`reinforcement` in `config.json`).
+/// Das Gewicht eines gelernten Beispiels ergibt sich aus seinen Treffern
+/// und Fehlschlägen, `base + hits * hit_bonus - misses * miss_penalty`,
+/// und halbiert sich je `half_life_days` ohne Bestätigung. Begrenzt auf
+/// `min_weight..=max_weight`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reinforcement {
+    /// Eine ähnliche Eingabe innerhalb dieser Zeit gilt als Umformulierung
+    pub rephrase_secs: u64,
+    /// Anteil gemeinsamer Wörter (Jaccard), ab dem eine Eingabe als
+    /// Umformulierung gilt
+    pub rephrase_overlap: f32,
+    pub hit_bonus: f32,
+    pub miss_penalty: f32,
+    pub min_weight: f32,
+    pub max_weight: f32,
+    /// Halbwertszeit unbestätigter Beispiele in Tagen (0 = kein Zerfall)
+    pub half_life_days: f32,
+}
+
+impl Default for Reinforcement {
+    fn default() -> Self {
+        Self {
+            rephrase_secs: 30,
+            rephrase_overlap: 0.5,
+            hit_bonus: 0.1,
+            miss_penalty: 0.4,
+            min_weight: 0.5,
+            max_weight: 3.0,
+            half_life_days: 30.0,
+        }
+    }
+}
+
+impl Reinforcement {
+    /// Gewicht eines Beispiels zum Zeitpunkt `now` (Unix-Sekunden)
+    pub fn weight(&self, sample: &LearnedSample, now: u64) -> f32 {
+        let mut w = sample.base + sample.hits as f32 * self.hit_bonus - sample.misses as f32 * self.miss_penalty;
+
+        let active = sample.last_active();
+        if self.half_life_days > 0.0 && active > 0 {
+            let idle_days = now.saturating_sub(active) as f32 / SECS_PER_DAY;
+            w *= 0.5f32.powf(idle_days / self.half_life_days);
+        }
+        w.clamp(self.min_weight, self.max_weight.max(self.min_weight))
+    }
+
+    /// Bewertet eine Aktion anhand der nächsten Eingabe. Eine schnelle,
+    /// ähnliche Eingabe ("wetter berlin" → "das wetter in berlin bitte")
+    /// heißt, dass die Aktion nicht gepasst hat.
+    pub fn judge(&self, previous: &str, next: &str, elapsed: Duration) -> Outcome {
+        let quick = elapsed < Duration::from_secs(self.rephrase_secs);
+        if quick && word_overlap(previous, next) >= self.rephrase_overlap {
+            Outcome::Miss
+        } else {
+            Outcome::Hit
+        }
+    }
+}
+
+/// Ausgeführte Aktion, deren Bewertung noch aussteht
+pub struct Feedback {
+    /// Index in `LearningStore::samples`
+    pub sample: usize,
+    pub text: String,
+    pub at: Instant,
+}
+
+/// Jaccard-Ähnlichkeit der normalisierten Wortmengen
+fn word_overlap(a: &str, b: &str) -> f32 {
+    let (a, b) = (normalize(a), normalize(b));
+    let a: HashSet<&str> = a.split_whitespace().collect();
+    let b: HashSet<&str> = b.split_whitespace().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct LearningStore {
-    pub samples: Vec<IntentSample>,
+    #[serde(default)]
+    pub version: u32,
+    pub samples: Vec<LearnedSample>,
+    /// Dokumenthäufigkeiten der gelernten Phrasen (für die IDF-Gewichtung)
+    #[serde(default)]
+    pub doc_stats: DocStats,
+}
+
+/// Dateiformat Version 1
+#[derive(Deserialize)]
+struct StoreV1 {
+    samples: Vec<IntentSample>,
 }
 
 impl LearningStore {
     pub fn new() -> Self {
-        Self { samples: Vec::new() }
+        Self { version: VERSION, samples: Vec::new(), doc_stats: DocStats::new() }
     }
 
     pub fn load() -> Self {
-        if let Ok(data) = fs::read_to_string(FILE) {
-            if let Ok(store) = serde_json::from_str(&data) {
+        let Ok(data) = fs::read_to_string(FILE) else {
+            return Self::new();
+        };
+
+        match Self::parse(&data) {
+            Ok((store, None)) => {
                 println!("Gelernte Daten geladen.");
-                return store;
+                store
+            }
+            Ok((store, Some(dropped))) => {
+                // Altes Format sichern, bevor es überschrieben wird
+                let backup = format!("{}.v1.bak", FILE);
+                if let Err(e) = fs::write(&backup, &data) {
+                    eprintln!("Sicherung {} nicht möglich: {} – alte Datei bleibt unverändert.", backup, e);
+                    return store;
+                }
+                eprintln!(
+                    "WARNUNG: {} hatte das alte Format ohne Phrasen (Sicherung: {}).\n\
+                     {} Beispiele übernommen, {} nur als Vektor verworfen – sie stammen aus dem \
+                     alten Vokabular und lassen sich nicht neu berechnen. Bitte bei Bedarf neu beibringen.",
+                    FILE, backup, store.samples.len(), dropped
+                );
+                store.save();
+                store
+            }
+            Err(e) => {
+                eprintln!("{} nicht lesbar: {} – starte ohne gelernte Daten.", FILE, e);
+                Self::new()
+            }
+        }
+    }
+
+    /// Liest beide Formate. Bei einer Migration enthält das Ergebnis die
+    /// Anzahl verworfener Beispiele ohne Phrase: ihre Vektoren stammen aus
+    /// dem alten Vokabular und wären nur noch Rauschen.
+    fn parse(data: &str) -> Result<(Self, Option<usize>), String> {
+        let value: serde_json::Value = serde_json::from_str(data).map_err(|e| e.to_string())?;
+        if value["version"].as_u64().unwrap_or(1) >= VERSION as u64 {
+            let store = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            return Ok((store, None));
+        }
+
+        let old: StoreV1 = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        let mut store = Self::new();
+        let mut dropped = 0;
+        for s in old.samples {
+            match s.phrase {
+                Some(phrase) => {
+                    // Alte Statistik zählt verworfene Beispiele mit, daher neu
+                    store.doc_stats.add_document(&phrase);
+                    store.samples.push(LearnedSample {
+                        intent: s.intent,
+                        phrase,
+                        weight: s.weight,
+                        base: s.weight,
+                        created: 0,
+                        source: SampleSource::Migrated,
+                        hits: 0,
+                        misses: 0,
+                        // Alter unbekannt: Zerfall ab der Übernahme
+                        last_used: now(),
+                    });
+                }
+                None => dropped += 1,
             }
         }
-        Self::new()
+        Ok((store, Some(dropped)))
     }
 
     pub fn save(&self) {
@@ -32,27 +282,245 @@ impl LearningStore {
         }
     }
 
+    /// Lernt eine Phrase und speichert. `similarity` vergleicht zwei
+    /// Phrasen (0 bis 1), siehe `insert`.
     pub fn add_phrase(
         &mut self,
-        vocab: &mut Vocab,
         intent: Intent,
         phrase: &str,
-    ) {
-        let vec = vocab.sentence_vec(phrase);
-        self.samples.push(IntentSample {
-            intent,
-            vector: vec,
-            weight: 2.0,
-        });
+        rules: &Compaction,
+        similarity: &mut dyn FnMut(&str, &str) -> f32,
+    ) -> Learned {
+        let learned = self.insert(intent, phrase, rules, similarity);
         self.save();
+        learned
+    }
+
+    /// Fügt eine Phrase ein. Fast gleiche Beispiele mit anderem Intent
+    /// widersprechen der neuen Anweisung und werden ersetzt; ist ein fast
+    /// gleiches Beispiel desselben Intents schon da, wird nur dieses
+    /// aufgefrischt statt ein Duplikat anzulegen. Die ausdrückliche
+    /// Anweisung hebt dabei frühere Fehlschläge auf.
+    fn insert(
+        &mut self,
+        intent: Intent,
+        phrase: &str,
+        rules: &Compaction,
+        similarity: &mut dyn FnMut(&str, &str) -> f32,
+    ) -> Learned {
+        let mut learned = Learned::default();
+        let mut i = 0;
+        while i < self.samples.len() {
+            let sample = &self.samples[i];
+            let sim = similarity(phrase, &sample.phrase);
+            if sample.intent != intent && sim >= rules.conflict_similarity {
+                let old = self.samples.remove(i);
+                self.doc_stats.remove_document(&old.phrase);
+                learned.replaced.push(old);
+                continue;
+            }
+            if sample.intent == intent && sim >= rules.merge_similarity && learned.merged_into.is_none() {
+                learned.merged_into = Some(i);
+            }
+            i += 1;
+        }
+
+        match learned.merged_into {
+            Some(i) => {
+                let sample = &mut self.samples[i];
+                sample.last_used = now();
+                sample.misses = 0;
+                sample.base = sample.base.max(INITIAL_WEIGHT);
+                sample.weight = sample.weight.max(INITIAL_WEIGHT);
+                sample.source = SampleSource::Taught;
+            }
+            None => {
+                // Der Klassifizierer übernimmt Phrase und Statistik beim nächsten Training
+                self.doc_stats.add_document(phrase);
+                self.samples.push(LearnedSample::taught(intent, phrase));
+            }
+        }
+        learned
     }
 
-    pub fn decay(&mut self) {
-        for s in &mut self.samples {
-            s.weight *= 0.995;
-            if s.weight < 0.5 {
-                s.weight = 0.5;
+    /// Zählt Treffer oder Fehlschlag eines Beispiels und passt sein Gewicht
+    /// an (ohne zu speichern). Ein Treffer setzt den Zerfall zurück. Gibt
+    /// zurück, ob sich das Gewicht geändert hat.
+    pub fn record(&mut self, index: usize, outcome: Outcome, rules: &Reinforcement, now: u64) -> bool {
+        let Some(sample) = self.samples.get_mut(index) else {
+            return false;
+        };
+        match outcome {
+            Outcome::Hit => {
+                sample.hits += 1;
+                sample.last_used = now;
             }
+            Outcome::Miss => sample.misses += 1,
         }
+
+        let old = sample.weight;
+        sample.weight = rules.weight(sample, now);
+        (old - sample.weight).abs() > f32::EPSILON
+    }
+
+    /// Berechnet die Gewichte nach der seit der letzten Bestätigung
+    /// vergangenen Zeit neu (ohne zu speichern). Hängt nur von den
+    /// Zeitstempeln ab, nicht davon, wie oft es aufgerufen wird. Gibt
+    /// zurück, ob sich ein Gewicht merklich geändert hat.
+    pub fn decay(&mut self, rules: &Reinforcement, now: u64) -> bool {
+        let mut changed = false;
+        for sample in &mut self.samples {
+            let weight = rules.weight(sample, now);
+            if (weight - sample.weight).abs() > DECAY_STEP {
+                sample.weight = weight;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Aktuelle Zeit in Unix-Sekunden
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_v1() {
+        let v1 = r#"{"samples": [
+            {"intent": "Weather", "vector": [0.1, 0.2], "weight": 1.5, "phrase": "wie siehts draußen aus"},
+            {"intent": "Greeting", "vector": [0.3, 0.4], "weight": 2.0}
+        ]}"#;
+        let (store, dropped) = LearningStore::parse(v1).unwrap();
+
+        // Reine Vektoren bleiben nur in der Sicherung
+        assert_eq!(dropped, Some(1));
+        assert_eq!(store.version, VERSION);
+        assert_eq!(store.samples.len(), 1);
+        assert_eq!(store.samples[0].phrase, "wie siehts draußen aus");
+        assert_eq!(store.samples[0].source, SampleSource::Migrated);
+        assert_eq!(store.doc_stats.docs, 1);
+
+        // Der Zerfall geht vom übernommenen Gewicht aus
+        let mut store = store;
+        let rules = Reinforcement::default();
+        let t = store.samples[0].last_used;
+        assert!(!store.decay(&rules, t));
+        assert_eq!(store.samples[0].weight, 1.5);
+        store.record(0, Outcome::Hit, &rules, t);
+        assert!((store.samples[0].weight - 1.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_roundtrip_without_vectors() {
+        let mut store = LearningStore::new();
+        store.samples.push(LearnedSample::taught(Intent::Search, "recherchier mal"));
+
+        let json = serde_json::to_string(&store).unwrap();
+        assert!(!json.contains("vector"));
+        let (loaded, dropped) = LearningStore::parse(&json).unwrap();
+        assert_eq!(dropped, None);
+        assert_eq!(loaded.samples[0].phrase, "recherchier mal");
+    }
+
+    #[test]
+    fn test_record_drives_weight() {
+        let rules = Reinforcement::default();
+        let mut store = LearningStore::new();
+        store.samples.push(LearnedSample::taught(Intent::Weather, "wie siehts draußen aus"));
+
+        let t = store.samples[0].created;
+
+        assert!(store.record(0, Outcome::Hit, &rules, t));
+        assert!((store.samples[0].weight - 2.1).abs() < 1e-5);
+        for _ in 0..10 {
+            store.record(0, Outcome::Miss, &rules, t);
+        }
+        assert_eq!(store.samples[0].misses, 10);
+        assert_eq!(store.samples[0].weight, rules.min_weight);
+        // Am Minimum ändert ein weiterer Fehlschlag nichts mehr
+        assert!(!store.record(0, Outcome::Miss, &rules, t));
+        assert!(!store.record(5, Outcome::Hit, &rules, t));
+    }
+
+    #[test]
+    fn test_judge_rephrase() {
+        let rules = Reinforcement { rephrase_secs: 10, ..Reinforcement::default() };
+        let quick = Duration::from_secs(3);
+
+        assert_eq!(rules.judge("wetter in berlin", "das wetter in berlin bitte", quick), Outcome::Miss);
+        assert_eq!(rules.judge("wetter in berlin", "öffne den editor", quick), Outcome::Hit);
+        // Später gilt auch eine ähnliche Frage als neue Anfrage
+        assert_eq!(rules.judge("wetter in berlin", "wetter in berlin", Duration::from_secs(60)), Outcome::Hit);
+    }
+
+    #[test]
+    fn test_half_life_decay() {
+        let rules = Reinforcement { half_life_days: 10.0, ..Reinforcement::default() };
+        let mut store = LearningStore::new();
+        store.samples.push(LearnedSample::taught(Intent::Weather, "wie siehts draußen aus"));
+        let t = store.samples[0].created;
+        let days = |n: u64| t + n * 86_400;
+
+        assert!(!store.decay(&rules, t));
+        assert!(store.decay(&rules, days(10)));
+        assert!((store.samples[0].weight - 1.0).abs() < 1e-4);
+        // Wiederholter Aufruf zum selben Zeitpunkt ändert nichts
+        assert!(!store.decay(&rules, days(10)));
+
+        // Bestätigung frischt auf
+        store.record(0, Outcome::Hit, &rules, days(10));
+        assert!((store.samples[0].weight - 2.1).abs() < 1e-4);
+        store.decay(&rules, days(100));
+        assert_eq!(store.samples[0].weight, rules.min_weight);
+
+        // Ohne Zeitstempel kein Zerfall
+        store.samples[0].created = 0;
+        store.samples[0].last_used = 0;
+        assert_eq!(rules.weight(&store.samples[0], days(100)), 2.1);
+    }
+
+    #[test]
+    fn test_insert_merges_and_replaces() {
+        let rules = Compaction::default();
+        let mut same_text = |a: &str, b: &str| if normalize(a) == normalize(b) { 1.0 } else { 0.0 };
+        let mut store = LearningStore::new();
+
+        store.insert(Intent::Weather, "wie siehts draußen aus", &rules, &mut same_text);
+        let learned = store.insert(Intent::Weather, "Wie sieht's draußen aus?", &rules, &mut same_text);
+        assert_eq!(learned.merged_into, Some(0));
+        assert_eq!(store.samples.len(), 1);
+        assert_eq!(store.doc_stats.docs, 1);
+
+        // Widerspruch: die neue Anweisung gewinnt
+        let learned = store.insert(Intent::Search, "wie siehts draußen aus", &rules, &mut same_text);
+        assert_eq!(learned.replaced.len(), 1);
+        assert_eq!(learned.replaced[0].intent, Intent::Weather);
+        assert_eq!(store.samples.len(), 1);
+        assert_eq!(store.samples[0].intent, Intent::Search);
+        assert_eq!(store.doc_stats.docs, 1);
+    }
+
+    #[test]
+    fn test_reteach_survives_decay() {
+        let rules = Compaction::default();
+        let reinforcement = Reinforcement::default();
+        let mut same_text = |a: &str, b: &str| if normalize(a) == normalize(b) { 1.0 } else { 0.0 };
+        let mut store = LearningStore::new();
+
+        store.insert(Intent::Weather, "wie siehts draußen aus", &rules, &mut same_text);
+        for _ in 0..3 {
+            store.record(0, Outcome::Miss, &reinforcement, now());
+        }
+        assert!(store.samples[0].weight < INITIAL_WEIGHT);
+
+        store.insert(Intent::Weather, "wie siehts draußen aus", &rules, &mut same_text);
+        store.decay(&reinforcement, now());
+        assert_eq!(store.samples[0].misses, 0);
+        assert!(store.samples[0].weight >= INITIAL_WEIGHT);
     }
 }
@@ -4,25 +4,63 @@
 
 use std::sync::mpsc::{self, Sender, Receiver};
 use std::thread;
+use std::time::Instant;
+use std::panic::AssertUnwindSafe;
 
 mod intent;
 mod similarity;
+mod spelling;
+mod synonyms;
 mod normalize;
 mod vector;
+mod embeddings;
+mod transformer;
 mod classifier;
+mod bayes;
+mod config;
 mod learning;
 mod boost;
 mod parser;
+mod samples;
 mod actions;
 mod gui;
+mod eval;
+mod thresholds;
+mod segment;
+mod compact;
 
 use intent::Intent;
-use vector::Vocab;
-use classifier::{IntentSample, classify};
-use learning::LearningStore;
-use parser::extract_location;
+use classifier::Classifier;
+use learning::{Feedback, LearningStore, Outcome};
+use config::Config;
+use normalize::normalize;
+use similarity::cosine_similarity;
+use boost::BoostRules;
+use thresholds::Thresholds;
+use parser::correction::Teaching;
+use parser::gazetteer::Gazetteer;
+use parser::slots::{self, SlotKind, Slots};
 
 fn main() {
+    // Kommandozeile: `Assistent eval [korpus.json] [--folds k]`,
+    // `Assistent calibrate [korpus.json]` oder `Assistent compact [--dry-run]`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("eval") => {
+            eval::run(&args[1..]);
+            return;
+        }
+        Some("calibrate") => {
+            thresholds::run(&args[1..]);
+            return;
+        }
+        Some("compact") => {
+            compact::run(&args[1..]);
+            return;
+        }
+        _ => {}
+    }
+
     // Channels für Kommunikation zwischen GUI und Backend
     let (gui_tx, backend_rx): (Sender<String>, Receiver<String>) = mpsc::channel();
     let (backend_tx, gui_rx): (Sender<String>, Receiver<String>) = mpsc::channel();
@@ -31,59 +69,50 @@ fn main() {
     // Stattdessen: Push-to-Talk Button in der GUI
     
     // Backend-Thread starten
-    let backend_tx_clone = backend_tx.clone();
     thread::spawn(move || {
-        run_backend(backend_rx, backend_tx_clone);
+        run_backend(backend_rx, backend_tx);
     });
 
     // GUI starten (blockiert den Main-Thread)
-    if let Err(e) = gui::create_window(gui_tx, gui_rx, backend_tx) {
+    if let Err(e) = gui::create_window(gui_tx, gui_rx) {
         eprintln!("GUI-Fehler: {}", e);
     }
 }
 
 fn run_backend(rx: Receiver<String>, tx: Sender<String>) {
-    let mut vocab = Vocab::new(32);
+    let config = Config::load();
     let mut learning = LearningStore::load();
+    let thresholds = Thresholds::load();
+    let mut boost_rules = BoostRules::load(boost::FILE).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        let _ = tx.send(format!("Boost-Regeln fehlerhaft, nutze Standardregeln.\n{}", e));
+        BoostRules::default()
+    });
     let mut is_active = false;
+    let mut pending: Option<FollowUp> = None;
+    // Letzter ausgeführter Teilsatz, Ziel von "Nein, ich meinte ..."
+    let mut last: Option<segment::Clause> = None;
+    // Zuletzt ausgeführte Aktion, bewertet anhand der nächsten Eingabe
+    let mut feedback: Option<Feedback> = None;
 
-    let static_samples = vec![
-        IntentSample {
-            intent: Intent::Greeting,
-            vector: vocab.sentence_vec("hallo hi hey guten tag morgen servus grüß"),
-            weight: 1.0,
-        },
-        IntentSample {
-            intent: Intent::Weather,
-            vector: vocab.sentence_vec("wetter temperatur grad celsius vorhersage klima regnet schneit scheint sonne"),
-            weight: 1.0,
-        },
-        IntentSample {
-            intent: Intent::Search,
-            vector: vocab.sentence_vec("suche finde informationen recherchiere was ist erkläre über wiki wissen"),
-            weight: 1.2,
-        },
-        IntentSample {
-            intent: Intent::FileOpen,
-            vector: vocab.sentence_vec("öffne starte start öffnen datei programm anwendung app ausführen"),
-            weight: 1.0,
-        },
-        IntentSample {
-            intent: Intent::FileShow,
-            vector: vocab.sentence_vec("zeige zeig anzeigen wo ist finde datei ordner mir"),
-            weight: 1.2,  // Erhöht
-        },
-        IntentSample {
-            intent: Intent::Goodbye,
-            vector: vocab.sentence_vec("tschüss danke bis später wiedersehen auf wiedersehen"),
-            weight: 1.0,
-        },
-        IntentSample {
-            intent: Intent::Shutdown,
-            vector: vocab.sentence_vec("schönen tag noch abmelden beenden ausschalten ende"),
-            weight: 1.0,
-        },
-    ];
+    // Seit dem letzten Start vergangene Zeit
+    if learning.decay(&config.reinforcement, learning::now()) {
+        learning.save();
+    }
+    let mut classifier = config.build_classifier();
+    classifier.train(&samples::training_set(&learning));
+    println!("Klassifizierer: {}", classifier.name());
+
+    if let Some(path) = &config.model_dump {
+        match classifier.to_json() {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(path, json) {
+                    eprintln!("Modell konnte nicht geschrieben werden: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Modell konnte nicht serialisiert werden: {}", e),
+        }
+    }
 
     let _ = tx.send("KI-Assistent gestartet. Warte auf Begrüßung...".to_string());
 
@@ -93,136 +122,370 @@ fn run_backend(rx: Receiver<String>, tx: Sender<String>) {
             continue;
         }
 
-        let input_vec = vocab.sentence_vec(&input);
-        let mut all_samples = Vec::new();
-        all_samples.extend_from_slice(&static_samples);
-        all_samples.extend_from_slice(&learning.samples);
-
-        let intent = classify(&input_vec, &input, &all_samples);
-        learning.decay();
-
-        match intent {
-            Intent::Greeting => {
-                if !is_active {
-                    is_active = true;
-                    let msg = "Hallo! Wie kann ich dir helfen?";
-                    let _ = tx.send("======================================".to_string());
-                    let _ = tx.send("   KI-Assistent aktiviert!".to_string());
-                    let _ = tx.send("======================================".to_string());
-                    let _ = tx.send(msg.to_string());
-                } else {
-                    let msg = "Hallo!";
-                    let _ = tx.send(msg.to_string());
-                }
+        // Geänderte Boost-Regeln ohne Neustart übernehmen
+        match boost_rules.reload_if_changed() {
+            Some(Ok(n)) => println!("Boost-Regeln neu geladen ({} Regeln).", n),
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                let _ = tx.send(format!("Boost-Regeln nicht übernommen:\n{}", e));
             }
-            
-            Intent::Weather => {
-                if !is_active {
-                    let _ = tx.send("[Bitte erst begrüßen]".to_string());
+            None => {}
+        }
+
+        let teaching = guarded(&tx, || parser::correction::parse_teaching(&input)).flatten();
+
+        // Gewichte nach Zeit seit der letzten Bestätigung
+        let now = learning::now();
+        let mut changed = learning.decay(&config.reinforcement, now);
+
+        // Widerspruch oder schnelle Umformulierung schwächt das Beispiel, das
+        // zur vorigen Aktion geführt hat, sonst wird es gestärkt
+        if let Some(done) = feedback.take() {
+            let objected = matches!(teaching, Some(Teaching::Previous(_) | Teaching::UnknownIntent(_)));
+            let outcome = if objected {
+                Outcome::Miss
+            } else {
+                config.reinforcement.judge(&done.text, &input, done.at.elapsed())
+            };
+            let reweighted = learning.record(done.sample, outcome, &config.reinforcement, now);
+            // Zähler haben sich in jedem Fall geändert
+            if !reweighted && !changed {
+                learning.save();
+            }
+            changed |= reweighted;
+        }
+        if changed {
+            learning.save();
+            classifier.train(&samples::training_set(&learning));
+        }
+
+        let clauses = match teaching {
+            // Vorige Äußerung unter dem richtigen Intent lernen und erneut ausführen
+            Some(Teaching::Previous(intent)) => {
+                let Some(mut clause) = last.take() else {
+                    let _ = tx.send("Ich habe noch nichts, das ich korrigieren könnte.".to_string());
                     continue;
+                };
+                teach(&mut learning, classifier.as_mut(), &config, &tx, intent.clone(), &clause.text);
+                pending = None;
+                clause.intent = intent;
+                vec![clause]
+            }
+            Some(Teaching::Phrase { phrase, intent }) => {
+                teach(&mut learning, classifier.as_mut(), &config, &tx, intent, &phrase);
+                continue;
+            }
+            Some(Teaching::UnknownIntent(name)) => {
+                let names = Intent::ALL.iter().map(|i| i.label()).collect::<Vec<_>>().join(", ");
+                let _ = tx.send(format!("„{}“ kenne ich nicht. Möglich sind: {}", name, names));
+                continue;
+            }
+            // Zusammengesetzte Befehle ("Hallo, wie ist das Wetter und öffne ...")
+            // werden Teilsatz für Teilsatz ausgeführt
+            None => {
+                let clauses = guarded(&tx, || {
+                    segment::classify_clauses(classifier.as_mut(), &boost_rules, &thresholds, &input)
+                });
+                let Some(clauses) = clauses else { continue };
+                clauses
+            }
+        };
+
+        // Antwort auf eine offene Rückfrage ("Für welchen Ort?" → "Berlin")
+        if let Some(follow_up) = pending.take() {
+            let answers = matches!(clauses.as_slice(),
+                [c] if c.intent == Intent::Unknown || c.intent == follow_up.intent);
+            if answers {
+                let reply = guarded(&tx, || {
+                    let mut slots = follow_up.slots;
+                    slots.answer(follow_up.kind, &input);
+                    execute(follow_up.intent, slots, &mut pending)
+                });
+                if let Some(reply) = reply {
+                    let _ = tx.send(reply);
                 }
-                
-                let location = extract_location(&input);
-                let weather_info = get_weather_info(location);
-                let _ = tx.send(weather_info);
+                continue;
             }
-            
-            Intent::Search => {
-                if !is_active {
-                    let _ = tx.send("[Bitte erst begrüßen]".to_string());
-                    continue;
+        }
+
+        for clause in &clauses {
+            let (input, result) = (&clause.text, &clause.result);
+
+            // Debug-Ausgabe auf der Konsole, nur im Debug-Build
+            if cfg!(debug_assertions) {
+                println!("Teilsatz \"{}\":", input);
+                for c in &result.corrections {
+                    println!("Korrigiert: {} → {}", c.from, c.to);
+                }
+                for c in result.top(3) {
+                    println!("{:?} (Beispiel {:?}): sim={:.3}, weight={:.2}, boost={:.2}, score={:.3}",
+                        c.intent, c.sample_index, c.similarity, c.weight, c.boost, c.score);
                 }
-                
-                let search_result = get_search_result(&input);
-                let _ = tx.send(search_result);
             }
+
+            if clauses.len() > 1 {
+                let _ = tx.send(format!("» {}", input));
+            }
+
+            match clause.intent {
+                Intent::Greeting => {
+                    if !is_active {
+                        is_active = true;
+                        let msg = "Hallo! Wie kann ich dir helfen?";
+                        let _ = tx.send("======================================".to_string());
+                        let _ = tx.send("   KI-Assistent aktiviert!".to_string());
+                        let _ = tx.send("======================================".to_string());
+                        let _ = tx.send(msg.to_string());
+                    } else {
+                        let msg = "Hallo!";
+                        let _ = tx.send(msg.to_string());
+                    }
+                }
             
-            Intent::FileOpen => {
-                if !is_active {
-                    let _ = tx.send("[Bitte erst begrüßen]".to_string());
-                    continue;
+                Intent::Weather => {
+                    if !is_active {
+                        let _ = tx.send("[Bitte erst begrüßen]".to_string());
+                        continue;
+                    }
+                
+                    let reply = guarded(&tx, || {
+                        let slots = slots::extract_slots(&Intent::Weather, input);
+                        execute(Intent::Weather, slots, &mut pending)
+                    });
+                    if let Some(reply) = reply {
+                        let _ = tx.send(reply);
+                    }
                 }
+            
+                Intent::Search => {
+                    if !is_active {
+                        let _ = tx.send("[Bitte erst begrüßen]".to_string());
+                        continue;
+                    }
                 
-                let result = open_file(&input);
-                let _ = tx.send(result);
-            }
+                    let reply = guarded(&tx, || {
+                        let slots = slots::extract_slots(&Intent::Search, input);
+                        execute(Intent::Search, slots, &mut pending)
+                    });
+                    if let Some(reply) = reply {
+                        let _ = tx.send(reply);
+                    }
+                }
             
-            Intent::FileShow => {
-                if !is_active {
-                    let _ = tx.send("[Bitte erst begrüßen]".to_string());
-                    continue;
+                Intent::FileOpen => {
+                    if !is_active {
+                        let _ = tx.send("[Bitte erst begrüßen]".to_string());
+                        continue;
+                    }
+                
+                    let reply = guarded(&tx, || {
+                        let slots = slots::extract_slots(&Intent::FileOpen, input);
+                        execute(Intent::FileOpen, slots, &mut pending)
+                    });
+                    if let Some(reply) = reply {
+                        let _ = tx.send(reply);
+                    }
                 }
+            
+                Intent::FileShow => {
+                    if !is_active {
+                        let _ = tx.send("[Bitte erst begrüßen]".to_string());
+                        continue;
+                    }
                 
-                let result = show_file(&input);
-                let _ = tx.send(result);
-            }
+                    let reply = guarded(&tx, || {
+                        let slots = slots::extract_slots(&Intent::FileShow, input);
+                        execute(Intent::FileShow, slots, &mut pending)
+                    });
+                    if let Some(reply) = reply {
+                        let _ = tx.send(reply);
+                    }
+                }
             
-            Intent::Goodbye => {
-                if is_active {
-                    let msg = "Bis bald! Ich laufe weiter im Hintergrund.";
-                    let _ = tx.send(msg.to_string());
-                    is_active = false;
+                Intent::Convert => {
+                    if !is_active {
+                        let _ = tx.send("[Bitte erst begrüßen]".to_string());
+                        continue;
+                    }
+                
+                    let reply = guarded(&tx, || {
+                        let slots = slots::extract_slots(&Intent::Convert, input);
+                        execute(Intent::Convert, slots, &mut pending)
+                    });
+                    if let Some(reply) = reply {
+                        let _ = tx.send(reply);
+                    }
                 }
-            }
             
-            Intent::Shutdown => {
-                let msg = "Auf Wiedersehen! Schönen Tag noch!";
-                let _ = tx.send(msg.to_string());
-                learning.save();
-                std::process::exit(0);
-            }
+                Intent::Goodbye => {
+                    if is_active {
+                        let msg = "Bis bald! Ich laufe weiter im Hintergrund.";
+                        let _ = tx.send(msg.to_string());
+                        is_active = false;
+                    }
+                }
             
-            Intent::Unknown => {
-                if !is_active {
-                    continue;
+                Intent::Shutdown => {
+                    let msg = "Auf Wiedersehen! Schönen Tag noch!";
+                    let _ = tx.send(msg.to_string());
+                    learning.save();
+                    std::process::exit(0);
                 }
+            
+                Intent::Unknown => {
+                    if !is_active {
+                        continue;
+                    }
                 
-                let msg = "Ich bin unsicher. Was meintest du?";
-                let _ = tx.send(msg.to_string());
+                    let guesses = result.top(3)
+                        .iter()
+                        .map(|c| format!("{} ({:.2})", c.intent.label(), c.score))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let msg = format!("Ich bin unsicher. Was meintest du? Vielleicht: {}", guesses);
+                    let _ = tx.send(msg);
+                    continue;
+                }
+            }
+
+            // Aktion ausgeführt (keine Rückfrage): Bewertung mit der nächsten Eingabe
+            let learned = result.best()
+                .filter(|best| best.intent == clause.intent)
+                .and_then(|best| best.sample_index)
+                .and_then(|i| samples::learned_index(&learning, i));
+            if let (Some(sample), None) = (learned, &pending) {
+                feedback = Some(Feedback { sample, text: input.clone(), at: Instant::now() });
             }
         }
+        last = clauses.last().cloned();
+    }
+}
+
+/// Lernt `phrase` als `intent`, trainiert neu und bestätigt es dem Nutzer.
+/// Fast gleiche Beispiele werden zusammengeführt, widersprechende ersetzt.
+fn teach(
+    learning: &mut LearningStore,
+    classifier: &mut dyn Classifier,
+    config: &Config,
+    tx: &Sender<String>,
+    intent: Intent,
+    phrase: &str,
+) {
+    // Ohne Satzvektoren (Naive Bayes) zählt nur gleicher Text
+    let mut similarity = |a: &str, b: &str| match (classifier.embed(a), classifier.embed(b)) {
+        (Some(x), Some(y)) => cosine_similarity(&x, &y),
+        _ => if normalize(a) == normalize(b) { 1.0 } else { 0.0 },
+    };
+    let learned = learning.add_phrase(intent.clone(), phrase, &config.compaction, &mut similarity);
+    let confirmation = match learned.merged_into.map(|i| &learning.samples[i].phrase) {
+        Some(known) if known != phrase => {
+            format!("{}„{}“ heißt {} (wie „{}“).", gui::LEARNED_PREFIX, phrase, intent.label(), known)
+        }
+        _ => format!("{}„{}“ heißt {}.", gui::LEARNED_PREFIX, phrase, intent.label()),
+    };
+
+    if compact::enforce_limit(learning, config.compaction.max_samples) > 0 {
+        learning.save();
+    }
+    classifier.train(&samples::training_set(learning));
+
+    let _ = tx.send(confirmation);
+    for old in learned.replaced {
+        let _ = tx.send(format!("Widerspruch: „{}“ hieß bisher {} – ersetzt.", old.phrase, old.intent.label()));
+    }
+}
+
+/// Führt `f` aus und fängt Panics ab, damit ein Fehler im Parser nicht
+/// den Backend-Thread beendet. Der Nutzer bekommt eine Fehlermeldung.
+///
+/// Nur die letzte Absicherung: Panics sind Fehler und werden mit den
+/// Fuzz-Tests in `parser` gesucht und dort behoben. Die Meldung samt Ort
+/// gibt der Standard-Panic-Hook weiter auf der Konsole aus.
+fn guarded<T>(tx: &Sender<String>, f: impl FnOnce() -> T) -> Option<T> {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            let _ = tx.send("Interner Fehler beim Verarbeiten der Eingabe.".to_string());
+            None
+        }
+    }
+}
+
+/// Offene Rückfrage nach einem fehlenden Slot
+struct FollowUp {
+    intent: Intent,
+    slots: Slots,
+    kind: SlotKind,
+}
+
+/// Führt einen Intent mit seinen Slots aus. Fehlt ein Pflicht-Slot, wird
+/// stattdessen nachgefragt und die Rückfrage vorgemerkt.
+fn execute(intent: Intent, slots: Slots, pending: &mut Option<FollowUp>) -> String {
+    if let Some(spec) = slots.missing(&intent).first() {
+        *pending = Some(FollowUp { intent, kind: spec.kind, slots });
+        return spec.question.to_string();
+    }
+
+    match intent {
+        Intent::Weather => get_weather_info(&slots),
+        Intent::Search => get_search_result(slots.text(SlotKind::Query).unwrap_or_default()),
+        Intent::FileOpen => {
+            let target = slots.text(SlotKind::Application).or(slots.text(SlotKind::Path));
+            open_file(target.unwrap_or_default())
+        }
+        Intent::FileShow => show_file(slots.text(SlotKind::Path).unwrap_or_default()),
+        Intent::Convert => convert_units(&slots),
+        _ => String::new(),
     }
 }
 
-fn get_weather_info(location: Option<String>) -> String {
+/// Koordinaten und Anzeigename über die Open-Meteo-Ortssuche
+fn geocode(loc: &str) -> Result<(f64, f64, String), String> {
     use reqwest::blocking::get;
     use serde_json::Value;
 
-    let (lat, lon, location_name) = if let Some(loc) = location.as_ref() {
-        // Geocoding
-        let url = format!(
-            "https://geocoding-api.open-meteo.com/v1/search?name={}&count=1&language=de&format=json",
-            loc.replace(" ", "%20")
-        );
-        
-        if let Ok(resp) = get(&url) {
-            if let Ok(json) = resp.json::<Value>() {
-                if let Some(results) = json["results"].as_array() {
-                    if let Some(first) = results.first() {
-                        if let (Some(lat), Some(lon)) = (
-                            first["latitude"].as_f64(),
-                            first["longitude"].as_f64()
-                        ) {
-                            let name = first["name"].as_str().unwrap_or(loc);
-                            (lat, lon, name.to_string())
-                        } else {
-                            return format!("Ort '{}' nicht gefunden.", loc);
-                        }
-                    } else {
-                        return format!("Ort '{}' nicht gefunden.", loc);
-                    }
-                } else {
-                    return format!("Ort '{}' nicht gefunden.", loc);
-                }
-            } else {
-                (50.83, 12.92, "Auma".to_string())
+    let url = format!(
+        "https://geocoding-api.open-meteo.com/v1/search?name={}&count=1&language=de&format=json",
+        loc.replace(" ", "%20")
+    );
+
+    match get(&url).ok().and_then(|resp| resp.json::<Value>().ok()) {
+        Some(json) => {
+            let first = json["results"].as_array().and_then(|r| r.first());
+            match first.map(|f| (f["latitude"].as_f64(), f["longitude"].as_f64(), f["name"].as_str())) {
+                Some((Some(lat), Some(lon), name)) => Ok((lat, lon, name.unwrap_or(loc).to_string())),
+                _ => Err(format!("Ort '{}' nicht gefunden.", loc)),
             }
-        } else {
-            (50.83, 12.92, "Auma".to_string())
         }
-    } else {
-        // Kein Ort angegeben - nutze Auma, Thüringen
-        (50.83, 12.92, "Auma".to_string())
+        None => Err("Ortssuche nicht erreichbar.".to_string()),
+    }
+}
+
+fn get_weather_info(slots: &Slots) -> String {
+    use reqwest::blocking::get;
+    use serde_json::Value;
+
+    // Pflicht-Slot, ohne Ort wird vorher nachgefragt
+    let loc = slots.text(SlotKind::Location).unwrap_or_default();
+    // Erster Tag (0 = heute) und Anzahl Tage der Vorhersage. Nur ein
+    // Zeitraum bestimmt die Anzahl, nicht jede Zahl ("bei 25 Grad").
+    let today = chrono::Local::now().date_naive();
+    let (first_day, days) = match slots.date_range() {
+        Some(range) => ((range.start.date() - today).num_days(), range.day_count()),
+        None => (0, 1),
+    };
+    if first_day < 0 {
+        return "Für vergangene Tage habe ich keine Vorhersage.".to_string();
+    }
+    let (first_day, days) = (first_day as usize, days.clamp(1, 7) as usize);
+
+    // Bekannte Orte ohne Netz auflösen, sonst Geocoding
+    let (lat, lon, location_name) = match Gazetteer::bundled().lookup(loc) {
+        Some(place) => (place.latitude, place.longitude, place.name.clone()),
+        None => match geocode(loc) {
+            Ok(found) => found,
+            Err(msg) => return msg,
+        },
     };
 
     let url = format!(
@@ -237,26 +500,41 @@ fn get_weather_info(location: Option<String>) -> String {
                 
                 result.push_str(&format!("Wetter für {}\n\n", location_name));
                 
-                if let Some(current) = json["current_weather"].as_object() {
-                    if let (Some(temp), Some(code)) = (
-                        current["temperature"].as_f64(),
-                        current["weathercode"].as_i64()
-                    ) {
-                        let weather_desc = weather_code_to_text(code);
-                        result.push_str(&format!("Aktuell: {:.1}°C, {}\n", temp, weather_desc));
+                if first_day == 0 {
+                    if let Some(current) = json["current_weather"].as_object() {
+                        if let (Some(temp), Some(code)) = (
+                            current["temperature"].as_f64(),
+                            current["weathercode"].as_i64()
+                        ) {
+                            let weather_desc = weather_code_to_text(code);
+                            result.push_str(&format!("Aktuell: {:.1}°C, {}\n", temp, weather_desc));
+                        }
                     }
                 }
                 
-                if let Some(daily) = json["daily"].as_object() {
+                let daily = &json["daily"];
+                let mut found = false;
+                for day in first_day..first_day + days {
                     if let (Some(max), Some(min)) = (
-                        daily["temperature_2m_max"].as_array().and_then(|a| a.first()?.as_f64()),
-                        daily["temperature_2m_min"].as_array().and_then(|a| a.first()?.as_f64())
+                        daily["temperature_2m_max"][day].as_f64(),
+                        daily["temperature_2m_min"][day].as_f64()
                     ) {
-                        result.push_str(&format!("Heute: {:.1}°C bis {:.1}°C", min, max));
+                        let label = match day {
+                            0 => "Heute".to_string(),
+                            1 => "Morgen".to_string(),
+                            2 => "Übermorgen".to_string(),
+                            _ => daily["time"][day].as_str().unwrap_or("?").to_string(),
+                        };
+                        let desc = daily["weathercode"][day].as_i64().map_or("unbekannt", weather_code_to_text);
+                        result.push_str(&format!("{}: {:.1}°C bis {:.1}°C, {}\n", label, min, max, desc));
+                        found = true;
                     }
                 }
+                if !found {
+                    result.push_str("Für diesen Tag gibt es noch keine Vorhersage.");
+                }
                 
-                result
+                result.trim_end().to_string()
             } else {
                 "Fehler beim Abrufen der Wetterdaten.".to_string()
             }
@@ -285,11 +563,9 @@ fn get_search_result(query: &str) -> String {
     use reqwest::blocking::Client;
     use serde_json::Value;
     
-    let search_term = parser::extract_search_query(query);
-    
     let url = format!(
         "https://de.wikipedia.org/api/rest_v1/page/summary/{}",
-        search_term.replace(" ", "_")
+        query.replace(" ", "_")
     );
 
     let client = Client::builder()
@@ -314,7 +590,7 @@ fn get_search_result(query: &str) -> String {
                             result.push_str(&format!("\n\nMehr unter: {}", url));
                         }
                     } else {
-                        result = format!("Keine Informationen zu '{}' gefunden.", search_term);
+                        result = format!("Keine Informationen zu '{}' gefunden.", query);
                     }
                     
                     result
@@ -322,15 +598,15 @@ fn get_search_result(query: &str) -> String {
                     "Fehler beim Verarbeiten der Antwort.".to_string()
                 }
             } else {
-                format!("Artikel '{}' nicht gefunden.", search_term)
+                format!("Artikel '{}' nicht gefunden.", query)
             }
         }
         Err(_) => "Recherche fehlgeschlagen.".to_string(),
     }
 }
 
-fn open_file(query: &str) -> String {
-    let file_name = parser::extract_file_path(query);
+fn open_file(target: &str) -> String {
+    let file_name = target.to_string();
     
     if file_name.is_empty() {
         return "Welche Datei soll ich öffnen?".to_string();
@@ -350,8 +626,8 @@ fn open_file(query: &str) -> String {
     result_msg
 }
 
-fn show_file(query: &str) -> String {
-    let file_name = parser::extract_file_path(query);
+fn show_file(target: &str) -> String {
+    let file_name = target.to_string();
     
     if file_name.is_empty() {
         return "Was soll ich anzeigen?".to_string();
@@ -369,4 +645,17 @@ fn show_file(query: &str) -> String {
     });
     
     result_msg
-}
\ No newline at end of file
+}
+
+/// "fünf Meilen in Kilometer" → "5 Meilen sind 8,05 km."
+fn convert_units(slots: &Slots) -> String {
+    // Beide Slots sind Pflicht, ohne sie wird vorher nachgefragt
+    let (Some(quantity), Some(to)) = (slots.quantity(), slots.unit()) else {
+        return String::new();
+    };
+
+    match quantity.convert(to) {
+        Some(result) => format!("{} sind {}.", quantity, result),
+        None => format!("{} lässt sich nicht in {} umrechnen.", quantity, to.symbol()),
+    }
+}
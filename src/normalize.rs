@@ -1,30 +1,244 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Bekannte Wortbestandteile für die Kompositazerlegung
+/// ("wetterbericht" → "wetter bericht"). Kleingeschrieben, mit Umlauten.
+const COMPOUND_PARTS: &[&str] = &[
+    // Wetter
+    "wetter", "bericht", "vorhersage", "prognose", "lage", "temperatur", "regen",
+    "schnee", "sonne", "wind", "sturm", "gewitter", "wolke", "nebel", "luft",
+    "druck", "hitze", "frost", "kälte", "schauer", "warnung", "radar", "karte",
+    // Zeit
+    "tag", "woche", "monat", "jahr", "morgen", "mittag", "abend", "nacht", "wochen",
+    "ende", "zeit", "stunde", "minute",
+    // Dateien & Programme
+    "datei", "dateien", "ordner", "programm", "dokument", "bild", "bilder", "foto",
+    "musik", "video", "text", "tabelle", "präsentation", "name", "pfad", "liste",
+    "browser", "fenster", "rechner", "speicher", "download", "desktop",
+    // Suche & Wissen
+    "suche", "such", "maschine", "frage", "wissen", "lexikon", "artikel", "seite",
+    "nachricht", "nachrichten", "information", "informationen",
+    // Allgemein
+    "haus", "stadt", "land", "welt", "heim", "arbeit", "auto", "bahn", "hof",
+    "straße", "platz", "markt", "schule", "ort", "system", "start", "end",
+];
+
+/// Fugenelemente zwischen zwei Bestandteilen ("arbeit-s-platz")
+const LINKING: &[&str] = &["", "s", "es", "n", "en"];
+
+/// Mindestlänge eines Bestandteils
+const MIN_PART: usize = 3;
+
 pub fn normalize(text: &str) -> String {
     let cleaned: String = text
+        .nfc()
+        .collect::<String>()
         .to_lowercase()
+        .replace('ß', "ss")
         .chars()
         .filter(|c| c.is_alphanumeric() || c.is_whitespace())
         .collect();
 
     cleaned
         .split_whitespace()
-        .map(stem)
+        .flat_map(split_compound)
+        .map(|w| stem(&w))
         .collect::<Vec<_>>()
         .join(" ")
 }
 
-fn stem(word: &str) -> String {
-    let mut w = word.to_string();
+/// Zerlegt ein Kompositum in bekannte Bestandteile.
+/// Unbekannte oder kurze Wörter bleiben unverändert.
+pub fn split_compound(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < 2 * MIN_PART || is_part(word) {
+        return vec![word.to_string()];
+    }
+
+    // best[i] = kürzeste Zerlegung von chars[..i]
+    let mut best: Vec<Option<Vec<String>>> = vec![None; chars.len() + 1];
+    best[0] = Some(Vec::new());
+
+    for end in MIN_PART..=chars.len() {
+        for start in 0..=end - MIN_PART {
+            let Some(prefix) = &best[start] else { continue };
+            let part: String = chars[start..end].iter().collect();
+
+            // Fugenelement am Ende des Teils abschneiden ("arbeits" → "arbeit")
+            let base = LINKING.iter().find_map(|l| {
+                let b = part.strip_suffix(l)?;
+                (b.chars().count() >= MIN_PART && is_part(b)).then(|| b.to_string())
+            });
+
+            if let Some(base) = base {
+                let mut parts = prefix.clone();
+                parts.push(base);
+                if best[end].as_ref().is_none_or(|b| parts.len() < b.len()) {
+                    best[end] = Some(parts);
+                }
+            }
+        }
+    }
+
+    match best.pop().flatten() {
+        Some(parts) if parts.len() > 1 => parts,
+        _ => vec![word.to_string()],
+    }
+}
+
+fn is_part(word: &str) -> bool {
+    let word = word.replace('ß', "ss");
+    COMPOUND_PARTS.iter().any(|p| p.replace('ß', "ss") == word)
+}
 
-    w = w.replace("ä", "a")
-         .replace("ö", "o")
-         .replace("ü", "u");
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'ä' | 'ö' | 'ü')
+}
 
-    for suffix in ["ern", "em", "er", "en", "es", "e", "n", "s"] {
-        if w.len() > suffix.len() + 2 && w.ends_with(suffix) {
-            w.truncate(w.len() - suffix.len());
-            break;
+fn valid_s_ending(c: char) -> bool {
+    matches!(c, 'b' | 'd' | 'f' | 'g' | 'h' | 'k' | 'l' | 'm' | 'n' | 'r' | 't')
+}
+
+fn valid_st_ending(c: char) -> bool {
+    matches!(c, 'b' | 'd' | 'f' | 'g' | 'h' | 'k' | 'l' | 'm' | 'n' | 't')
+}
+
+/// Beginn der Region nach der ersten Nicht-Vokal-auf-Vokal-Folge ab `from`
+fn region_start(w: &[char], from: usize) -> usize {
+    let mut i = from + 1;
+    while i < w.len() {
+        if !is_vowel(w[i]) && is_vowel(w[i - 1]) {
+            return i + 1;
         }
+        i += 1;
     }
+    w.len()
+}
+
+fn ends_with(w: &[char], suffix: &str) -> bool {
+    let s: Vec<char> = suffix.chars().collect();
+    w.len() >= s.len() && w[w.len() - s.len()..] == s[..]
+}
+
+/// Deutscher Snowball-Stemmer (https://snowballstem.org/algorithms/german/stemmer.html)
+pub fn stem(word: &str) -> String {
+    let mut w: Vec<char> = word.replace('ß', "ss").chars().collect();
 
-    w
+    // u und y zwischen Vokalen sind Konsonanten
+    for i in 1..w.len().saturating_sub(1) {
+        if is_vowel(w[i - 1]) && is_vowel(w[i + 1]) {
+            if w[i] == 'u' {
+                w[i] = 'U';
+            } else if w[i] == 'y' {
+                w[i] = 'Y';
+            }
+        }
+    }
+
+    // R2 wird ab dem unkorrigierten R1 gesucht, R1 umfasst mindestens 3 Zeichen davor
+    let r1_raw = region_start(&w, 0);
+    let r1 = r1_raw.max(3);
+    let r2 = region_start(&w, r1_raw);
+
+    // Schritt 1
+    if let Some(s) = ["ern", "em", "er"].into_iter().find(|s| ends_with(&w, s)) {
+        if w.len() - s.len() >= r1 {
+            w.truncate(w.len() - s.len());
+        }
+    } else if let Some(s) = ["en", "es", "e"].into_iter().find(|s| ends_with(&w, s)) {
+        if w.len() - s.len() >= r1 {
+            w.truncate(w.len() - s.len());
+            if ends_with(&w, "niss") {
+                w.pop();
+            }
+        }
+    } else if ends_with(&w, "s") && w.len() > r1 && w.len() >= 2 && valid_s_ending(w[w.len() - 2]) {
+        w.pop();
+    }
+
+    // Schritt 2
+    if let Some(s) = ["en", "er", "est"].into_iter().find(|s| ends_with(&w, s)) {
+        if w.len() - s.len() >= r1 {
+            w.truncate(w.len() - s.len());
+        }
+    } else if ends_with(&w, "st") && w.len() - 2 >= r1 && w.len() >= 6 && valid_st_ending(w[w.len() - 3]) {
+        w.truncate(w.len() - 2);
+    }
+
+    // Schritt 3: Ableitungssuffixe
+    let in_r1 = |w: &[char], len: usize| w.len() - len >= r1;
+    let in_r2 = |w: &[char], len: usize| w.len() - len >= r2;
+
+    if let Some(s) = ["end", "ung"].into_iter().find(|s| ends_with(&w, s)) {
+        if in_r2(&w, 3) {
+            w.truncate(w.len() - s.len());
+            if ends_with(&w, "ig") && in_r2(&w, 2) && !ends_with(&w[..w.len() - 2], "e") {
+                w.truncate(w.len() - 2);
+            }
+        }
+    } else if let Some(s) = ["isch", "ig", "ik"].into_iter().find(|s| ends_with(&w, s)) {
+        let len = s.chars().count();
+        if in_r2(&w, len) && !ends_with(&w[..w.len() - len], "e") {
+            w.truncate(w.len() - len);
+        }
+    } else if let Some(s) = ["lich", "heit"].into_iter().find(|s| ends_with(&w, s)) {
+        if in_r2(&w, 4) {
+            w.truncate(w.len() - s.len());
+            if let Some(p) = ["er", "en"].into_iter().find(|p| ends_with(&w, p)) {
+                if in_r1(&w, 2) {
+                    w.truncate(w.len() - p.len());
+                }
+            }
+        }
+    } else if ends_with(&w, "keit") && in_r2(&w, 4) {
+        w.truncate(w.len() - 4);
+        if let Some(p) = ["lich", "ig"].into_iter().find(|p| ends_with(&w, p)) {
+            let len = p.chars().count();
+            if in_r2(&w, len) {
+                w.truncate(w.len() - len);
+            }
+        }
+    }
+
+    w.into_iter()
+        .map(|c| match c {
+            'U' | 'ü' => 'u',
+            'Y' => 'y',
+            'ä' => 'a',
+            'ö' => 'o',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(stem("häuser"), stem("haus"));
+        assert_eq!(stem("wetter"), stem("wetters"));
+        assert_eq!(stem("öffnen"), stem("öffne"));
+        assert_eq!(stem("aufeinanderfolgenden"), "aufeinanderfolg");
+        assert_eq!(stem("kategorischen"), "kategor");
+        assert_eq!(stem("tag"), "tag");
+        assert_eq!(stem("ist"), "ist");
+    }
+
+    #[test]
+    fn test_compounds() {
+        assert_eq!(split_compound("wetterbericht"), vec!["wetter", "bericht"]);
+        assert_eq!(split_compound("wettervorhersage"), vec!["wetter", "vorhersage"]);
+        assert_eq!(split_compound("arbeitsplatz"), vec!["arbeit", "platz"]);
+        assert_eq!(split_compound("wetter"), vec!["wetter"]);
+        assert_eq!(split_compound("hallo"), vec!["hallo"]);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Wettervorhersage"), normalize("Wetter Vorhersage"));
+        assert_eq!(normalize("Straße"), normalize("strasse"));
+        // "ä" als Basisbuchstabe + kombinierendes Trema (NFD)
+        assert_eq!(normalize("Ha\u{0308}user"), normalize("Häuser"));
+    }
 }
@@ -0,0 +1,87 @@
+use crate::intent::Intent;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Eine Korrektur oder Lernanweisung des Nutzers
+#[derive(Debug, Clone, PartialEq)]
+pub enum Teaching {
+    /// "Nein, ich meinte Wetter" – die vorige Äußerung war so gemeint
+    Previous(Intent),
+    /// "lerne: 'wie siehts draußen aus' heißt Wetter"
+    Phrase { phrase: String, intent: Intent },
+    /// Als Korrektur erkannt, aber der Intent-Name ist unbekannt
+    UnknownIntent(String),
+}
+
+fn previous() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)^(?:nein|falsch|quatsch)[\s,!.]+(?:ich meinte|gemeint war|ich wollte|das war|meinte)\s+(?P<intent>.+?)[\s.!]*$")
+            .expect("Korrektur-Regex ist gültig")
+    })
+}
+
+fn phrase() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)^lerne?\b\s*(?P<colon>:)?\s*(?P<phrase>.+)\s+(?:heißt|bedeutet|ist)\s+(?P<intent>\S.*?)[\s.!]*$")
+            .expect("Lern-Regex ist gültig")
+    })
+}
+
+/// Erkennt Korrekturen und Lernanweisungen, `None` für normale Äußerungen
+pub fn parse_teaching(text: &str) -> Option<Teaching> {
+    let text = text.trim();
+
+    let is_quote = |c: char| matches!(c, '"' | '\'' | '„' | '“' | '”' | '‚' | '‘' | '’');
+
+    // Nur eindeutige Anweisungen: "lerne:" bzw. "lerne" mit Phrase in
+    // Anführungszeichen, Korrekturen nur nach "nein"/"falsch". Sonst wären
+    // "ich wollte beenden" oder "lern mal, was ist Wetter" Korrekturen.
+    let (phrase, name) = if let Some(caps) = phrase().captures(text) {
+        let raw = caps["phrase"].trim();
+        if caps.name("colon").is_none() && !raw.starts_with(is_quote) {
+            return None;
+        }
+        (Some(raw.trim_matches(is_quote).trim().to_string()), caps["intent"].to_string())
+    } else if let Some(caps) = previous().captures(text) {
+        (None, caps["intent"].to_string())
+    } else {
+        return None;
+    };
+
+    let teaching = match (Intent::from_name(&name), phrase) {
+        (None, _) => Teaching::UnknownIntent(name),
+        (Some(_), Some(phrase)) if phrase.is_empty() => return None,
+        (Some(intent), Some(phrase)) => Teaching::Phrase { phrase, intent },
+        (Some(intent), None) => Teaching::Previous(intent),
+    };
+    Some(teaching)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_teaching() {
+        assert_eq!(parse_teaching("Nein, ich meinte Wetter"), Some(Teaching::Previous(Intent::Weather)));
+        assert_eq!(parse_teaching("Falsch, ich meinte die Suche!"), Some(Teaching::Previous(Intent::Search)));
+        assert_eq!(
+            parse_teaching("lerne: 'wie siehts draußen aus' heißt Wetter"),
+            Some(Teaching::Phrase { phrase: "wie siehts draußen aus".to_string(), intent: Intent::Weather })
+        );
+        assert_eq!(
+            parse_teaching("Lerne „mach den Editor auf“ bedeutet Datei öffnen."),
+            Some(Teaching::Phrase { phrase: "mach den Editor auf".to_string(), intent: Intent::FileOpen })
+        );
+        assert_eq!(parse_teaching("nein, ich meinte Kaffee"), Some(Teaching::UnknownIntent("Kaffee".to_string())));
+        assert_eq!(parse_teaching("Wie ist das Wetter in Berlin?"), None);
+        assert_eq!(parse_teaching("lernen ist wichtig"), None);
+        assert_eq!(parse_teaching("ich wollte fragen, wie spät es ist"), None);
+        assert_eq!(parse_teaching("ich wollte suchen"), None);
+        assert_eq!(parse_teaching("ich wollte beenden"), None);
+        assert_eq!(parse_teaching("das war Wetter"), None);
+        assert_eq!(parse_teaching("Lern mal, was ist Wetter"), None);
+    }
+}
@@ -0,0 +1,397 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use super::number;
+
+/// Zeitangaben weiter als etwa zehn Jahre (relativ oder als Datum) werden
+/// verworfen
+const MAX_DAYS: i64 = 3660;
+
+/// Normalisierter Zeitraum `[start, end)`. Zeitpunkte ("um halb acht")
+/// haben `start == end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl DateRange {
+    /// Ganze Tage ab `first`, `None` außerhalb des darstellbaren Bereichs
+    pub fn days(first: NaiveDate, count: i64) -> Option<Self> {
+        let start = first.and_time(NaiveTime::MIN);
+        let end = start.checked_add_signed(Duration::try_days(count)?)?;
+        Some(Self { start, end })
+    }
+
+    pub fn at(time: NaiveDateTime) -> Self {
+        Self { start: time, end: time }
+    }
+
+    pub fn is_instant(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Anzahl angebrochener Kalendertage (mindestens 1)
+    pub fn day_count(&self) -> i64 {
+        let last = if self.is_instant() { self.end } else { self.end - Duration::nanoseconds(1) };
+        (last.date() - self.start.date()).num_days() + 1
+    }
+}
+
+const WEEKDAYS: &[(&str, Weekday)] = &[
+    ("montag", Weekday::Mon),
+    ("dienstag", Weekday::Tue),
+    ("mittwoch", Weekday::Wed),
+    ("donnerstag", Weekday::Thu),
+    ("freitag", Weekday::Fri),
+    ("samstag", Weekday::Sat),
+    ("sonnabend", Weekday::Sat),
+    ("sonntag", Weekday::Sun),
+];
+
+/// Tageszeiten als Stundenbereich (Ende > 24 reicht in den nächsten Tag)
+const PARTS_OF_DAY: &[(&str, u32, u32)] = &[
+    ("früh", 6, 10),
+    ("morgens", 6, 10),
+    ("vormittag", 9, 12),
+    ("vormittags", 9, 12),
+    ("mittag", 12, 14),
+    ("mittags", 12, 14),
+    ("nachmittag", 14, 18),
+    ("nachmittags", 14, 18),
+    ("abend", 18, 22),
+    ("abends", 18, 22),
+    ("nacht", 22, 30),
+    ("nachts", 22, 30),
+];
+
+/// Relative Tage
+const DAYS: &[(&str, i64)] = &[
+    ("vorgestern", -2),
+    ("gestern", -1),
+    ("heute", 0),
+    ("morgen", 1),
+    ("übermorgen", 2),
+];
+
+const NEXT: &[&str] = &["nächsten", "nächste", "kommenden", "kommende"];
+
+/// Ganze Zahl für Uhrzeiten ("acht", "15")
+fn small_number(word: &str) -> Option<u32> {
+    number::parse_cardinal(word)
+        .filter(|n| n.fract() == 0.0 && (0.0..=60.0).contains(n))
+        .map(|n| n as u32)
+}
+
+/// Zeitspanne am Anfang von `tokens` ("3 tagen", "einer halben stunde"):
+/// (Sekunden, verbrauchte Wörter). Höchstens `MAX_DAYS`.
+fn time_amount(tokens: &[String]) -> Option<(i64, usize)> {
+    let (quantity, used) = number::quantity_at(tokens)?;
+    let seconds = quantity.seconds().filter(|s| s.abs() <= (MAX_DAYS * 86400) as f64)?;
+    Some((seconds.round() as i64, used))
+}
+
+/// Wörter, mit denen eine Zeitangabe beginnt (für das Abschneiden von
+/// Ortsnamen: "Berlin am Freitag" → "Berlin")
+pub fn is_time_word(word: &str) -> bool {
+    let w = word.to_lowercase();
+    matches!(w.as_str(), "am" | "um" | "ab" | "bis" | "halb" | "viertel" | "dreiviertel")
+        || NEXT.contains(&w.as_str())
+        || DAYS.iter().any(|(d, _)| *d == w)
+        || WEEKDAYS.iter().any(|(d, _)| *d == w)
+        || PARTS_OF_DAY.iter().any(|(p, _, _)| *p == w)
+        || w.starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn tokens(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            let w = w.trim_start_matches(|c: char| !c.is_alphanumeric());
+            // Punkte gehören zu Daten ("24.12."), nicht zu Wörtern ("morgen.")
+            let keep_dot = w.starts_with(|c: char| c.is_ascii_digit());
+            w.trim_end_matches(|c: char| !(c.is_alphanumeric() || keep_dot && c == '.'))
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// "24.12." / "24.12.2025" / "24.12", höchstens `MAX_DAYS` von heute
+fn parse_date(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let parts: Vec<&str> = token.trim_end_matches('.').split('.').collect();
+    let (day, month) = match parts.as_slice() {
+        [d, m] | [d, m, _] => (d.parse().ok()?, m.parse().ok()?),
+        _ => return None,
+    };
+
+    let date = match parts.get(2) {
+        Some(y) => {
+            let year: i32 = y.parse().ok()?;
+            let year = if year < 100 { 2000 + year } else { year };
+            NaiveDate::from_ymd_opt(year, month, day)
+        }
+        // Ohne Jahr: nächstes Vorkommen
+        None => {
+            let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+            if date < today {
+                NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+            } else {
+                Some(date)
+            }
+        }
+    };
+    date.filter(|d| (*d - today).num_days().abs() <= MAX_DAYS)
+}
+
+/// "15:30" / "15.30" / "15"
+fn parse_clock_digits(token: &str) -> Option<(u32, u32)> {
+    let token = token.trim_end_matches('.');
+    let (h, m): (u32, u32) = match token.split_once([':', '.']) {
+        Some((h, m)) => (h.parse().ok()?, m.parse().ok()?),
+        None => (token.parse().ok()?, 0),
+    };
+    (h <= 24 && m < 60).then_some((h % 24, m))
+}
+
+/// Uhrzeit ab `tokens[i]`: (Stunde, Minute, verbrauchte Tokens).
+/// `explicit` gilt nach "um" – dann reicht eine nackte Zahl.
+fn parse_clock(tokens: &[String], i: usize, explicit: bool) -> Option<(u32, u32, usize)> {
+    let word = |k: usize| tokens.get(i + k).map(String::as_str);
+    let hour = |k: usize| word(k).and_then(small_number).filter(|h| (1..=12).contains(h));
+
+    match word(0)? {
+        "halb" => Some(((hour(1)? + 11) % 12, 30, 2)),
+        "dreiviertel" => Some(((hour(1)? + 11) % 12, 45, 2)),
+        "viertel" => match word(1)? {
+            "nach" => Some((hour(2)?, 15, 3)),
+            "vor" => Some(((hour(2)? + 11) % 12, 45, 3)),
+            // Ostdeutsch: "viertel acht" = 7:15
+            _ => Some(((hour(1)? + 11) % 12, 15, 2)),
+        },
+        w => {
+            let (h, m) = parse_clock_digits(w).or_else(|| Some((small_number(w).filter(|h| *h <= 24)?, 0)))?;
+            if word(1) == Some("uhr") {
+                // "8 uhr 30"
+                match word(2).and_then(|m| m.parse::<u32>().ok()).filter(|m| *m < 60) {
+                    Some(minutes) if m == 0 => Some((h, minutes, 3)),
+                    _ => Some((h, m, 2)),
+                }
+            } else if explicit || w.contains(':') {
+                Some((h, m, 1))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn next_weekday(from: NaiveDate, weekday: Weekday, strictly_after: bool) -> NaiveDate {
+    let mut days = (weekday.num_days_from_monday() as i64 - from.weekday().num_days_from_monday() as i64).rem_euclid(7);
+    if days == 0 && strictly_after {
+        days = 7;
+    }
+    from + Duration::days(days)
+}
+
+/// Erkennt deutsche Datums- und Zeitangaben relativ zu `now`:
+/// heute/morgen/übermorgen, Wochentage ("am Freitag", "nächsten Montag"),
+/// Abstände ("in 3 Tagen", "in zwei Stunden", "die nächsten 3 Tage",
+/// "für drei Tage"),
+/// Daten ("am 24.12."), Uhrzeiten ("um halb acht", "um 15:30 Uhr") und
+/// Tageszeiten ("Freitag Nachmittag", "morgen früh").
+///
+/// Ohne Uhrzeit ist das Ergebnis der ganze Tag bzw. die Tageszeit.
+pub fn parse_datetime(text: &str, now: NaiveDateTime) -> Option<DateRange> {
+    let tokens = tokens(text);
+    let today = now.date();
+
+    let mut date: Option<NaiveDate> = None;
+    let mut span_days = 1;
+    let mut part: Option<(u32, u32)> = None;
+    let mut clock: Option<(u32, u32)> = None;
+    // Abstand ab jetzt, als Zeitraum ("nächsten 2 stunden") oder Zeitpunkt
+    let mut offset: Option<(Duration, bool)> = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let w = tokens[i].as_str();
+        let prev = i.checked_sub(1).map(|p| tokens[p].as_str());
+        let next = tokens.get(i + 1).map(String::as_str);
+        let mut used = 1;
+
+        if w == "morgen" && (date.is_some() || prev == Some("guten")) {
+            // "heute morgen", "Freitag morgen"; "guten Morgen" ist keine Zeitangabe
+            if prev != Some("guten") {
+                part = Some((6, 10));
+            }
+        } else if let Some((_, d)) = DAYS.iter().find(|(d, _)| *d == w) {
+            date = Some(today + Duration::days(*d));
+        } else if let Some((_, day)) = WEEKDAYS.iter().find(|(d, _)| *d == w) {
+            let strictly = prev.is_some_and(|p| NEXT.contains(&p));
+            date = Some(next_weekday(today, *day, strictly));
+        } else if let Some((_, from, to)) = PARTS_OF_DAY.iter().find(|(p, _, _)| *p == w) {
+            part = Some((*from, *to));
+        } else if let Some(d) = parse_date(w, today) {
+            // "24.12." – aber nicht "15.30 uhr" nach "um"
+            if prev == Some("um") || next == Some("uhr") {
+                if let Some((h, m, n)) = parse_clock(&tokens, i, true) {
+                    clock = Some((h, m));
+                    used = n;
+                }
+            } else {
+                date = Some(d);
+            }
+        } else if let Some((seconds, n)) = time_amount(&tokens[i + 1..]).filter(|_| w == "in" || w == "für" || NEXT.contains(&w)) {
+            // "in 3 tagen" (Zeitpunkt) / "nächsten 3 tage", "für 3 tage" (Zeitraum)
+            let span = w != "in";
+            used = n + 1;
+            if seconds > 0 && seconds % 86400 == 0 {
+                let days = seconds / 86400;
+                if span {
+                    date = Some(today);
+                    span_days = days;
+                } else {
+                    date = Some(today.checked_add_signed(Duration::try_days(days)?)?);
+                }
+            } else {
+                offset = Some((Duration::try_seconds(seconds)?, span));
+            }
+        } else if let Some((h, m, n)) = parse_clock(&tokens, i, prev == Some("um")) {
+            clock = Some((h, m));
+            used = n;
+        }
+
+        i += used;
+    }
+
+    if let Some((d, span)) = offset {
+        let then = now.checked_add_signed(d)?;
+        return Some(if span { DateRange { start: now, end: then } } else { DateRange::at(then) });
+    }
+
+    let base = date.unwrap_or(today);
+
+    if let Some((mut h, m)) = clock {
+        let at = |date: NaiveDate, h: u32| date.and_hms_opt(h, m, 0);
+
+        if let Some((from, _)) = part {
+            // "halb acht abends" → 19:30
+            if from >= 12 && h < 12 {
+                h += 12;
+            }
+        } else if date.is_none() && h <= 12 {
+            // Ohne Datum und Tageszeit: das nächste passende Vorkommen
+            let candidates = [at(today, h), at(today, (h + 12) % 24), at(today + Duration::days(1), h)];
+            return candidates.into_iter().flatten().find(|t| *t >= now).map(DateRange::at);
+        }
+        return at(base, h).map(DateRange::at);
+    }
+
+    if let Some((from, to)) = part {
+        let midnight = base.and_time(NaiveTime::MIN);
+        let start = midnight.checked_add_signed(Duration::try_hours(from as i64)?)?;
+        let end = midnight.checked_add_signed(Duration::try_hours(to as i64)?)?;
+        return Some(DateRange { start, end });
+    }
+
+    date.and_then(|d| DateRange::days(d, span_days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mittwoch, 15.10.2025, 9:00
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 10, 15).unwrap().and_hms_opt(9, 0, 0).unwrap()
+    }
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+    }
+
+    fn at(m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        date(m, d).and_hms_opt(h, min, 0).unwrap()
+    }
+
+    fn parse(text: &str) -> Option<DateRange> {
+        parse_datetime(text, now())
+    }
+
+    #[test]
+    fn test_relative_days() {
+        assert_eq!(parse("Wetter heute"), DateRange::days(date(10, 15), 1));
+        assert_eq!(parse("Wetter morgen"), DateRange::days(date(10, 16), 1));
+        assert_eq!(parse("übermorgen in Jena"), DateRange::days(date(10, 17), 1));
+        assert_eq!(parse("in 3 Tagen"), DateRange::days(date(10, 18), 1));
+        assert_eq!(parse("in zwei Wochen"), DateRange::days(date(10, 29), 1));
+        assert_eq!(parse("die nächsten 3 Tage"), DateRange::days(date(10, 15), 3));
+        assert_eq!(parse("Wetter für drei Tage"), DateRange::days(date(10, 15), 3));
+        assert_eq!(parse("guten Morgen"), None);
+        assert_eq!(parse("wie ist das Wetter"), None);
+    }
+
+    #[test]
+    fn test_huge_numbers() {
+        assert_eq!(parse("in 100000000 tagen"), None);
+        assert_eq!(parse("die nächsten 100000000 tage"), None);
+        assert_eq!(parse("in 9999999999999999 sekunden"), None);
+        assert_eq!(parse("in 99999999999 Wochen"), None);
+        assert_eq!(parse("in 3000 Tagen").unwrap().start.date(), NaiveDate::from_ymd_opt(2034, 1, 1).unwrap());
+        // Daten zu weit weg gelten nicht, der Rest wird ohne sie gelesen
+        assert_eq!(parse("Wetter am 31.12.262142 nachts").map(|r| r.start.date()), Some(date(10, 15)));
+        assert_eq!(parse("am 1.1.9999"), None);
+        assert_eq!(parse("am 1.1.2030").unwrap().start.date(), NaiveDate::from_ymd_opt(2030, 1, 1).unwrap());
+        // Tageszeit über das Kalenderende hinaus
+        let last = NaiveDate::MAX.and_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(parse_datetime("heute nachts", last), None);
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(parse("am Freitag").unwrap().start.date(), date(10, 17));
+        // Heute ist Mittwoch
+        assert_eq!(parse("Mittwoch").unwrap().start.date(), date(10, 15));
+        assert_eq!(parse("nächsten Mittwoch").unwrap().start.date(), date(10, 22));
+        assert_eq!(parse("am Montag").unwrap().start.date(), date(10, 20));
+    }
+
+    #[test]
+    fn test_absolute_dates() {
+        assert_eq!(parse("am 24.12."), DateRange::days(date(12, 24), 1));
+        assert_eq!(parse("am 1.3.2026").unwrap().start.date(), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        // Schon vorbei: nächstes Jahr
+        assert_eq!(parse("am 1.5.").unwrap().start.date(), NaiveDate::from_ymd_opt(2026, 5, 1).unwrap());
+        assert_eq!(parse("am 31.2."), None);
+    }
+
+    #[test]
+    fn test_times() {
+        assert_eq!(parse("um 15 Uhr"), Some(DateRange::at(at(10, 15, 15, 0))));
+        assert_eq!(parse("um 15:30"), Some(DateRange::at(at(10, 15, 15, 30))));
+        assert_eq!(parse("morgen um 8 Uhr 30"), Some(DateRange::at(at(10, 16, 8, 30))));
+        // 7:30 ist schon vorbei, also abends
+        assert_eq!(parse("um halb acht"), Some(DateRange::at(at(10, 15, 19, 30))));
+        assert_eq!(parse("viertel nach zehn"), Some(DateRange::at(at(10, 15, 10, 15))));
+        assert_eq!(parse("morgen um viertel vor neun"), Some(DateRange::at(at(10, 16, 8, 45))));
+        assert_eq!(parse("Freitag halb acht abends"), Some(DateRange::at(at(10, 17, 19, 30))));
+        assert_eq!(parse("in 20 Minuten"), Some(DateRange::at(at(10, 15, 9, 20))));
+        assert_eq!(parse("erinnere mich in zwanzig Minuten"), Some(DateRange::at(at(10, 15, 9, 20))));
+        assert_eq!(parse("in einer halben Stunde"), Some(DateRange::at(at(10, 15, 9, 30))));
+        assert_eq!(parse("in anderthalb Stunden"), Some(DateRange::at(at(10, 15, 10, 30))));
+    }
+
+    #[test]
+    fn test_parts_of_day() {
+        let range = parse("am Freitag Nachmittag").unwrap();
+        assert_eq!((range.start, range.end), (at(10, 17, 14, 0), at(10, 17, 18, 0)));
+
+        let range = parse("heute Morgen").unwrap();
+        assert_eq!((range.start, range.end), (at(10, 15, 6, 0), at(10, 15, 10, 0)));
+
+        let range = parse("morgen früh").unwrap();
+        assert_eq!(range.start, at(10, 16, 6, 0));
+
+        let range = parse("heute Nacht").unwrap();
+        assert_eq!((range.start, range.end), (at(10, 15, 22, 0), at(10, 16, 6, 0)));
+        assert_eq!(range.day_count(), 2);
+    }
+}
@@ -0,0 +1,195 @@
+//! Mitgeliefertes Ortsverzeichnis (`data/gazetteer.tsv`, Auszug aus
+//! GeoNames). Erkennt Ortsnamen überall in der Äußerung ("Berlin Wetter",
+//! "Wetter Hamburg morgen"), auch mit Tippfehlern ("Müchen").
+
+use super::{tokenize, datetime};
+use crate::similarity::edit_distance;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const DATA: &str = include_str!("../../data/gazetteer.tsv");
+
+/// Ortsnamen, die auch gewöhnliche Wörter sind ("Essen", "Halle"). Sie
+/// zählen nur direkt hinter einer Ortspräposition.
+const COMMON_WORDS: &[&str] = &["essen", "halle", "siegen"];
+
+/// Präpositionen vor einem Ort
+const PREPOSITIONS: &[&str] = &["in", "für", "von", "bei", "nach", "aus"];
+
+/// Mindestlänge für ungenaue Treffer ("haben" ist nicht "Hagen")
+const MIN_FUZZY_LEN: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub name: String,
+    /// ISO-Ländercode
+    pub country: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub population: u64,
+}
+
+pub struct Gazetteer {
+    places: Vec<Place>,
+    /// Gefaltete Namen und Alternativnamen → Orte, einwohnerstärkster zuerst
+    names: HashMap<String, Vec<usize>>,
+    /// Längster Name in Wörtern ("Frankfurt am Main" = 3)
+    max_words: usize,
+}
+
+impl Gazetteer {
+    /// Das mitgelieferte Verzeichnis, beim ersten Zugriff geladen
+    pub fn bundled() -> &'static Gazetteer {
+        static GAZETTEER: OnceLock<Gazetteer> = OnceLock::new();
+        GAZETTEER.get_or_init(|| Self::parse(DATA).expect("data/gazetteer.tsv fehlerhaft"))
+    }
+
+    /// Liest Tab-getrennte Zeilen: Name, Alternativnamen (Komma-getrennt),
+    /// Land, Breite, Länge, Einwohner. `#` leitet Kommentare ein.
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let mut places = Vec::new();
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (n, line) in data.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [name, alternatives, country, lat, lon, population] = fields[..] else {
+                return Err(format!("Zeile {}: 6 Spalten erwartet, {} gefunden", n + 1, fields.len()));
+            };
+            let number = |s: &str| s.trim().parse::<f64>().map_err(|e| format!("Zeile {}: '{}': {}", n + 1, s, e));
+
+            let index = places.len();
+            places.push(Place {
+                name: name.trim().to_string(),
+                country: country.trim().to_string(),
+                latitude: number(lat)?,
+                longitude: number(lon)?,
+                population: number(population)? as u64,
+            });
+
+            let all = std::iter::once(name).chain(alternatives.split(','));
+            for key in all.map(key).filter(|k| !k.is_empty()) {
+                let entry = names.entry(key).or_default();
+                if !entry.contains(&index) {
+                    entry.push(index);
+                }
+            }
+        }
+
+        for indices in names.values_mut() {
+            indices.sort_by_key(|&i| std::cmp::Reverse(places[i].population));
+        }
+        let max_words = names.keys().map(|k| k.split(' ').count()).max().unwrap_or(1);
+
+        Ok(Self { places, names, max_words })
+    }
+
+    /// Ort mit genau diesem Namen (Groß-/Kleinschreibung und Umlaut-
+    /// schreibweise egal)
+    pub fn lookup(&self, name: &str) -> Option<&Place> {
+        self.names.get(&key(name)).map(|indices| &self.places[indices[0]])
+    }
+
+    /// Erster Ort in der Äußerung. Exakte Treffer (längster Name zuerst)
+    /// gehen vor ungenauen; ungenau wird nur bei großgeschriebenen Wörtern
+    /// oder hinter einer Präposition gesucht.
+    pub fn find(&self, text: &str) -> Option<&Place> {
+        let tokens = tokenize(text);
+        let keys: Vec<String> = tokens.iter().map(|t| fold(&t.lower)).collect();
+        let after_preposition = |i: usize| i > 0 && PREPOSITIONS.contains(&tokens[i - 1].lower.as_str());
+        let allowed = |key: &str, i: usize| !COMMON_WORDS.contains(&key) || after_preposition(i);
+
+        for i in 0..keys.len() {
+            for len in (1..=self.max_words.min(keys.len() - i)).rev() {
+                let name = keys[i..i + len].join(" ");
+                if let Some(indices) = self.names.get(&name) {
+                    if len > 1 || allowed(&name, i) {
+                        return Some(&self.places[indices[0]]);
+                    }
+                }
+            }
+        }
+
+        (0..keys.len())
+            .filter(|&i| tokens[i].text.starts_with(char::is_uppercase) || after_preposition(i))
+            .filter(|&i| !datetime::is_time_word(&tokens[i].lower))
+            .find_map(|i| self.fuzzy(&keys[i]).filter(|_| allowed(&keys[i], i)))
+    }
+
+    /// Einwortname mit kleinstem Tippabstand (gleicher Anfangsbuchstabe,
+    /// 1 Fehler, ab 8 Zeichen 2)
+    fn fuzzy(&self, word: &str) -> Option<&Place> {
+        let len = word.chars().count();
+        if len < MIN_FUZZY_LEN {
+            return None;
+        }
+        let max_distance = if len >= 8 { 2 } else { 1 };
+        let first = word.chars().next();
+
+        self.names
+            .iter()
+            .filter(|(name, _)| !name.contains(' ') && name.chars().next() == first)
+            .filter(|(name, _)| !COMMON_WORDS.contains(&name.as_str()))
+            .map(|(name, indices)| (edit_distance(word, name), &self.places[indices[0]]))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, place)| (*distance, std::cmp::Reverse(place.population)))
+            .map(|(_, place)| place)
+    }
+}
+
+/// Vergleichsform eines Namens aus mehreren Wörtern
+fn key(name: &str) -> String {
+    tokenize(name)
+        .iter()
+        .map(|t| fold(&t.lower))
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "München" und "Muenchen" werden gleich verglichen
+fn fold(word: &str) -> String {
+    word.replace('ß', "ss")
+        .replace('ä', "ae")
+        .replace('ö', "oe")
+        .replace('ü', "ue")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(text: &str) -> Option<String> {
+        Gazetteer::bundled().find(text).map(|p| p.name.clone())
+    }
+
+    #[test]
+    fn test_places_anywhere() {
+        assert_eq!(find("Berlin Wetter"), Some("Berlin".to_string()));
+        assert_eq!(find("Wetter Hamburg morgen"), Some("Hamburg".to_string()));
+        assert_eq!(find("wie wird es in frankfurt am main?"), Some("Frankfurt am Main".to_string()));
+        assert_eq!(find("Wetter in Muenchen"), Some("München".to_string()));
+        assert_eq!(find("Wie warm wird es um 15 Uhr"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_and_common_words() {
+        assert_eq!(find("Wetter Müchen"), Some("München".to_string()));
+        assert_eq!(find("wetter in hamburk"), Some("Hamburg".to_string()));
+        // Kleingeschrieben ohne Präposition kein ungenauer Treffer
+        assert_eq!(find("was haben wir heute"), None);
+        assert_eq!(find("Hallo, wie ist das Wetter?"), None);
+        assert_eq!(find("Wetter nach dem Essen"), None);
+        assert_eq!(find("Wetter in Essen"), Some("Essen".to_string()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Gazetteer::parse("Berlin\t\tDE\t52.5").is_err());
+        assert!(Gazetteer::parse("Berlin\t\tDE\tNord\t13.4\t1").is_err());
+        let g = Gazetteer::parse("# Kommentar\nWien\tVienna\tAT\t48.2\t16.4\t1900000").unwrap();
+        assert_eq!(g.lookup("vienna").map(|p| p.country.as_str()), Some("AT"));
+    }
+}
@@ -0,0 +1,241 @@
+pub mod correction;
+pub mod datetime;
+pub mod gazetteer;
+pub mod number;
+pub mod slots;
+
+use std::ops::Range;
+
+/// Ein Wort des Originaltexts mit seinem Byte-Bereich. Verglichen wird
+/// über `lower`, ausgeschnitten immer am Original – so bleiben Grenzen
+/// gültig, auch wenn Kleinschreibung die Länge ändert ("İ" → "i̇").
+pub struct Token<'a> {
+    pub text: &'a str,
+    /// Kleingeschrieben, ohne Satzzeichen am Rand
+    pub lower: String,
+    pub span: Range<usize>,
+}
+
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in input.char_indices().chain(std::iter::once((input.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                let text = &input[s..i];
+                let lower = text
+                    .trim_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase();
+                tokens.push(Token { text, lower, span: s..i });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Position der ersten Wortfolge `marker` in `tokens`
+fn find_words(tokens: &[Token], marker: &[&str]) -> Option<usize> {
+    (0..tokens.len()).find(|&i| starts_with_words(&tokens[i..], marker))
+}
+
+fn starts_with_words(tokens: &[Token], marker: &[&str]) -> bool {
+    tokens.len() >= marker.len() && tokens.iter().zip(marker).all(|(t, m)| t.lower == *m)
+}
+
+/// Rest des Originaltexts ab Token `i` (leer, wenn es keines gibt)
+fn rest_from<'a>(input: &'a str, tokens: &[Token], i: usize) -> &'a str {
+    tokens.get(i).map_or("", |t| &input[t.span.start..])
+}
+
+/// Suchbegriffe ohne Befehlswörter, `None` wenn nur ein Befehl übrig bleibt
+pub fn search_terms(input: &str) -> Option<String> {
+    // Typische Befehlswörter am Anfang
+    let stopwords: &[&[&str]] = &[
+        &["suche", "nach"], &["suche"], &["finde"], &["finde", "informationen"],
+        &["informationen", "über"], &["über"], &["recherchiere"],
+        &["was", "ist", "ein"], &["was", "ist", "eine"], &["was", "ist", "der"],
+        &["was", "ist", "die"], &["was", "ist", "das"],
+        &["was", "ist"], &["wer", "ist"], &["erkläre", "mir"], &["erkläre"],
+    ];
+    
+    let tokens = tokenize(input);
+    let mut i = 0;
+    
+    // Mehrere Befehlswörter hintereinander ("finde informationen über ...")
+    while let Some(marker) = stopwords.iter().find(|m| starts_with_words(&tokens[i..], m)) {
+        i += marker.len();
+    }
+    
+    // Entferne Fragezeichen und Satzzeichen am Ende
+    let cleaned = rest_from(input, &tokens, i)
+        .to_lowercase()
+        .trim_end_matches(['?', '.', '!'])
+        .trim()
+        .to_string();
+    
+    // Nur Befehlswörter ("suche", "was ist")
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+/// Extrahiert einen Ortsnamen aus einer Wetteranfrage
+pub fn extract_location(input: &str) -> Option<String> {
+    let tokens = tokenize(input);
+    
+    // Suche nach Präpositionen, die einen Ort anzeigen ("um" ist fast
+    // immer eine Uhrzeit: "um 15 Uhr")
+    let markers = ["in", "für", "von", "bei"];
+    
+    for marker in markers {
+        let Some(pos) = find_words(&tokens, &[marker]) else { continue };
+        
+        // Ort endet am ersten Satzzeichen
+        let mut words = Vec::new();
+        for t in &tokens[pos + 1..] {
+            let word = t.text.trim_start_matches(|c: char| !c.is_alphanumeric());
+            match word.find(['.', '?', '!', ',']) {
+                Some(end) => {
+                    words.push(&word[..end]);
+                    break;
+                }
+                None => words.push(word),
+            }
+        }
+        
+        let location = words.join(" ").trim().to_string();
+        if !location.is_empty() {
+            return Some(location);
+        }
+    }
+    
+    None
+}
+
+/// Extrahiert einen Datei-/Ordnernamen
+pub fn extract_file_path(input: &str) -> String {
+    match file_target(input) {
+        Some((_, target)) => target,
+        None => input.trim().to_string(),
+    }
+}
+
+/// Ziel hinter einem Befehlswort als (Befehlswort, Ziel), z.B.
+/// ("starte", "firefox"). Das Ziel ist leer, wenn nichts folgt.
+pub fn file_target(input: &str) -> Option<(&'static str, String)> {
+    let tokens = tokenize(input);
+    
+    // Suche nach typischen Mustern - längste zuerst!
+    let markers: [&'static [&'static str]; 7] = [
+        &["zeige", "mir"],
+        &["zeig", "mir"],
+        &["öffne"],
+        &["zeige"],
+        &["zeig"],
+        &["starte"],
+        &["start"],
+    ];
+    
+    for marker in markers {
+        if let Some(pos) = find_words(&tokens, marker) {
+            let after = rest_from(input, &tokens, pos + marker.len());
+            return Some((marker[0], after.trim().to_string()));
+        }
+    }
+    
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intent::Intent;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_extract_search() {
+        assert_eq!(search_terms("suche nach Rust Programmierung"), Some("rust programmierung".to_string()));
+        assert_eq!(search_terms("finde Informationen über Berlin"), Some("berlin".to_string()));
+        assert_eq!(search_terms("suche"), None);
+    }
+
+    #[test]
+    fn test_extract_location() {
+        assert_eq!(extract_location("Wie ist das Wetter in Berlin?"), Some("Berlin".to_string()));
+        assert_eq!(extract_location("Wetter für München"), Some("München".to_string()));
+        // "in" nur als ganzes Wort, nicht in "Berlin"
+        assert_eq!(extract_location("Berlin Wetter"), None);
+        assert_eq!(extract_location("Wie warm wird es um 15 Uhr"), None);
+    }
+
+    #[test]
+    fn test_lowercase_changes_length() {
+        // "İ" wird kleingeschrieben zu "i̇" (3 statt 2 Bytes), "ẞ" zu "ß"
+        assert_eq!(extract_location("İİİ Wetter in İzmir?"), Some("İzmir".to_string()));
+        assert_eq!(search_terms("Suche nach STRAẞE"), Some("straße".to_string()));
+        assert_eq!(extract_file_path("İİİ öffne Ärger.txt"), "Ärger.txt");
+        assert_eq!(search_terms("ẞẞẞ"), Some("ßßß".to_string()));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_extractors_never_panic(input in "\\PC*") {
+            let _ = search_terms(&input);
+            let _ = extract_location(&input);
+            let _ = extract_file_path(&input);
+            for intent in Intent::ALL {
+                let _ = slots::extract_slots(&intent, &input);
+            }
+            let _ = number::parse_quantities(&input);
+            let _ = datetime::parse_datetime(&input, chrono::Local::now().naive_local());
+            let _ = crate::segment::split_clauses(&input);
+        }
+
+        #[test]
+        fn prop_extractors_with_markers(
+            prefix in "[İẞÄöü a-zA-Z\\PC]{0,12}",
+            marker in prop::sample::select(vec!["in", "für", "suche nach", "öffne", "zeig mir", "starte", "um"]),
+            rest in "\\PC{0,20}",
+        ) {
+            let input = format!("{} {} {}", prefix, marker, rest);
+
+            // Ergebnisse stammen aus dem Original, Dateipfade unverändert
+            let path = extract_file_path(&input);
+            prop_assert!(input.contains(&path));
+            if let Some(location) = extract_location(&input) {
+                prop_assert!(!location.is_empty());
+            }
+            let _ = search_terms(&input);
+            let _ = datetime::parse_datetime(&input, chrono::Local::now().naive_local());
+        }
+
+        /// Zahlen aller Größen in Zeitangaben ("in 99999999999 Wochen")
+        #[test]
+        fn prop_dates_with_numbers(
+            marker in prop::sample::select(vec!["in", "die nächsten", "nächste", "um", "am", "für", "ab"]),
+            number in prop_oneof![
+                any::<i64>().prop_map(|n| n.to_string()),
+                "[0-9]{1,30}",
+                "[0-9]{1,12}[.:,][0-9]{1,12}\\.?[0-9]{0,12}",
+                prop::sample::select(vec!["einer halben", "anderthalb", "zwanzig", "tausend"]).prop_map(String::from),
+            ],
+            unit in prop::sample::select(vec![
+                "sekunden", "minuten", "stunden", "tagen", "tage", "wochen", "monaten", "jahren", "uhr", "nachts", "abends", "",
+            ]),
+        ) {
+            let input = format!("Wetter {} {} {}", marker, number, unit);
+            if let Some(range) = datetime::parse_datetime(&input, chrono::Local::now().naive_local()) {
+                let _ = range.day_count();
+            }
+            let _ = number::parse_quantities(&input);
+            let slots = slots::extract_slots(&Intent::Weather, &input);
+            let _ = slots.date_range().map(|r| r.day_count());
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,528 @@
+//! Zahlen und Mengenangaben: "dreiundzwanzig", "zweieinhalb", "die dritte",
+//! "3,5 km", "eine halbe Stunde", "fünf Meilen".
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Volume,
+    Temperature,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Millimeter,
+    Centimeter,
+    Meter,
+    Kilometer,
+    Mile,
+    Foot,
+    Inch,
+    Gram,
+    Kilogram,
+    Pound,
+    Ton,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Milliliter,
+    Liter,
+    Celsius,
+    Fahrenheit,
+}
+
+/// Schreibweisen der Einheiten (kleingeschrieben, inkl. Plural und Flexion)
+const UNITS: &[(&[&str], Unit)] = &[
+    (&["mm", "millimeter", "millimetern"], Unit::Millimeter),
+    (&["cm", "zentimeter", "zentimetern"], Unit::Centimeter),
+    (&["m", "meter", "metern"], Unit::Meter),
+    (&["km", "kilometer", "kilometern"], Unit::Kilometer),
+    (&["meile", "meilen"], Unit::Mile),
+    (&["fuß", "fuss"], Unit::Foot),
+    (&["zoll"], Unit::Inch),
+    (&["g", "gramm"], Unit::Gram),
+    (&["kg", "kilo", "kilogramm"], Unit::Kilogram),
+    (&["pfund"], Unit::Pound),
+    (&["t", "tonne", "tonnen"], Unit::Ton),
+    (&["s", "sek", "sekunde", "sekunden"], Unit::Second),
+    (&["min", "minute", "minuten"], Unit::Minute),
+    (&["h", "std", "stunde", "stunden"], Unit::Hour),
+    (&["tag", "tage", "tagen"], Unit::Day),
+    (&["woche", "wochen"], Unit::Week),
+    (&["ml", "milliliter"], Unit::Milliliter),
+    (&["l", "liter", "litern"], Unit::Liter),
+    (&["grad", "°c", "celsius"], Unit::Celsius),
+    (&["°f", "fahrenheit"], Unit::Fahrenheit),
+];
+
+impl Unit {
+    pub fn parse(word: &str) -> Option<Unit> {
+        let word = word.to_lowercase();
+        UNITS.iter().find(|(names, _)| names.contains(&word.as_str())).map(|(_, unit)| *unit)
+    }
+
+    /// Kurzform für Antworten ("km", "°C")
+    pub fn symbol(&self) -> &'static str {
+        use Unit::*;
+        match self {
+            Millimeter => "mm",
+            Centimeter => "cm",
+            Meter => "m",
+            Kilometer => "km",
+            Mile => "Meilen",
+            Foot => "Fuß",
+            Inch => "Zoll",
+            Gram => "g",
+            Kilogram => "kg",
+            Pound => "Pfund",
+            Ton => "t",
+            Second => "s",
+            Minute => "min",
+            Hour => "h",
+            Day => "Tage",
+            Week => "Wochen",
+            Milliliter => "ml",
+            Liter => "l",
+            Celsius => "°C",
+            Fahrenheit => "°F",
+        }
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        use Unit::*;
+        match self {
+            Millimeter | Centimeter | Meter | Kilometer | Mile | Foot | Inch => Dimension::Length,
+            Gram | Kilogram | Pound | Ton => Dimension::Mass,
+            Second | Minute | Hour | Day | Week => Dimension::Time,
+            Milliliter | Liter => Dimension::Volume,
+            Celsius | Fahrenheit => Dimension::Temperature,
+        }
+    }
+
+    /// Faktor zur Basiseinheit (Meter, Kilogramm, Sekunde, Liter).
+    /// Temperaturen werden gesondert umgerechnet.
+    fn factor(&self) -> f64 {
+        use Unit::*;
+        match self {
+            Millimeter => 0.001,
+            Centimeter => 0.01,
+            Meter => 1.0,
+            Kilometer => 1000.0,
+            Mile => 1609.344,
+            Foot => 0.3048,
+            Inch => 0.0254,
+            Gram => 0.001,
+            Kilogram => 1.0,
+            Pound => 0.5,
+            Ton => 1000.0,
+            Second => 1.0,
+            Minute => 60.0,
+            Hour => 3600.0,
+            Day => 86400.0,
+            Week => 604800.0,
+            Milliliter => 0.001,
+            Liter => 1.0,
+            Celsius | Fahrenheit => 1.0,
+        }
+    }
+}
+
+/// Eine Zahl mit optionaler Einheit. Ordinalzahlen ("die dritte")
+/// haben keine Einheit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Option<Unit>,
+    pub ordinal: bool,
+}
+
+impl Quantity {
+    /// Wert in Sekunden, falls die Einheit eine Zeit ist
+    pub fn seconds(&self) -> Option<f64> {
+        let unit = self.unit.filter(|u| u.dimension() == Dimension::Time)?;
+        Some(self.value * unit.factor())
+    }
+
+    /// Rechnet in eine andere Einheit derselben Dimension um
+    pub fn convert(&self, to: Unit) -> Option<Quantity> {
+        let from = self.unit?;
+        if from.dimension() != to.dimension() {
+            return None;
+        }
+
+        let value = match (from, to) {
+            (Unit::Celsius, Unit::Fahrenheit) => self.value * 9.0 / 5.0 + 32.0,
+            (Unit::Fahrenheit, Unit::Celsius) => (self.value - 32.0) * 5.0 / 9.0,
+            _ => self.value * from.factor() / to.factor(),
+        };
+        Some(Quantity { value, unit: Some(to), ordinal: false })
+    }
+}
+
+/// Deutsche Schreibweise mit höchstens zwei Nachkommastellen ("8,05 km")
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = format!("{:.2}", self.value);
+        let value = value.trim_end_matches('0').trim_end_matches('.').replace('.', ",");
+        match self.unit {
+            Some(unit) => write!(f, "{} {}", value, unit.symbol()),
+            None => write!(f, "{}", value),
+        }
+    }
+}
+
+const ONES: &[&str] = &[
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+    "zehn", "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn",
+    "achtzehn", "neunzehn",
+];
+
+const TENS: &[(&str, u64)] = &[
+    ("zwanzig", 20),
+    ("dreissig", 30),
+    ("vierzig", 40),
+    ("fünfzig", 50),
+    ("sechzig", 60),
+    ("siebzig", 70),
+    ("achtzig", 80),
+    ("neunzig", 90),
+];
+
+/// Artikel, die als "1" gelten, wenn eine Einheit oder ein Bruch folgt
+const ARTICLES: &[&str] = &["ein", "eine", "einen", "einem", "einer"];
+
+/// 0..99 ohne Bindestriche ("einundzwanzig")
+fn below_hundred(word: &str) -> Option<u64> {
+    if word == "ein" {
+        return Some(1);
+    }
+    if let Some(n) = ONES.iter().position(|w| *w == word) {
+        return Some(n as u64);
+    }
+    for (tens_word, tens) in TENS {
+        if word == *tens_word {
+            return Some(*tens);
+        }
+        if let Some(unit) = word.strip_suffix(tens_word).and_then(|w| w.strip_suffix("und")) {
+            let unit = below_hundred(unit).filter(|u| (1..=9).contains(u))?;
+            return Some(tens + unit);
+        }
+    }
+    None
+}
+
+/// Teil vor "hundert"/"tausend": leer oder "ein" ist 1
+fn multiplier(word: &str, parse: fn(&str) -> Option<u64>) -> Option<u64> {
+    match word {
+        "" | "ein" | "eine" => Some(1),
+        w => parse(w),
+    }
+}
+
+fn below_thousand(word: &str) -> Option<u64> {
+    match word.split_once("hundert") {
+        Some((hundreds, rest)) => {
+            let hundreds = multiplier(hundreds, below_hundred).filter(|h| *h < 10)?;
+            let rest = if rest.is_empty() { 0 } else { below_hundred(rest.trim_start_matches("und"))? };
+            Some(hundreds * 100 + rest)
+        }
+        None => below_hundred(word),
+    }
+}
+
+fn below_million(word: &str) -> Option<u64> {
+    match word.split_once("tausend") {
+        Some((thousands, rest)) => {
+            let thousands = multiplier(thousands, below_thousand)?;
+            let rest = if rest.is_empty() { 0 } else { below_thousand(rest.trim_start_matches("und"))? };
+            Some(thousands * 1000 + rest)
+        }
+        None => below_thousand(word),
+    }
+}
+
+/// Ganze Zahl als Wort, z.B. "dreiundzwanzig", "zweihundertfünf",
+/// "viertausenddreihundert"
+pub fn parse_integer_word(word: &str) -> Option<u64> {
+    let word = word.to_lowercase().replace('ß', "ss");
+    if word.is_empty() {
+        return None;
+    }
+    below_million(&word)
+}
+
+/// Brüche: "halb", "viertel", "anderthalb", "zweieinhalb"
+fn parse_fraction(word: &str) -> Option<f64> {
+    match word {
+        "halb" | "halbe" | "halben" | "halber" | "halbes" => Some(0.5),
+        "viertel" => Some(0.25),
+        "dreiviertel" => Some(0.75),
+        "drittel" => Some(1.0 / 3.0),
+        "anderthalb" => Some(1.5),
+        w => {
+            let whole = w.strip_suffix("einhalb")?;
+            Some(parse_integer_word(whole)? as f64 + 0.5)
+        }
+    }
+}
+
+/// Ziffern mit deutschem Dezimalkomma und Tausenderpunkten
+/// ("3", "3,5", "1.000", "1.000,25")
+fn parse_digits(word: &str) -> Option<f64> {
+    if !word.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let (int, frac) = match word.split_once(',') {
+        Some((i, f)) => (i, Some(f)),
+        None => (word, None),
+    };
+
+    // Punkte nur als Tausendertrennzeichen (Dreiergruppen), sonst Dezimalpunkt
+    let groups: Vec<&str> = int.split('.').collect();
+    let int = if groups.len() > 1 && groups[1..].iter().all(|g| g.len() == 3) {
+        groups.concat()
+    } else {
+        int.to_string()
+    };
+
+    let number = match frac {
+        Some(f) => format!("{}.{}", int, f),
+        None => int,
+    };
+    number.parse().ok()
+}
+
+/// Grundzahl als Ziffern, Wort oder Bruch
+pub fn parse_cardinal(word: &str) -> Option<f64> {
+    let word = word.to_lowercase();
+    parse_digits(&word)
+        .or_else(|| parse_fraction(&word))
+        .or_else(|| Some(parse_integer_word(&word)? as f64))
+}
+
+/// Ordnungszahl: "erste", "dritten", "siebte", "zwanzigster", "3."
+pub fn parse_ordinal(word: &str) -> Option<u64> {
+    let word = word.to_lowercase().replace('ß', "ss");
+
+    if let Some(digits) = word.strip_suffix('.') {
+        return digits.parse().ok();
+    }
+
+    const ENDINGS: &[&str] = &["e", "er", "en", "em", "es"];
+    let stem = ENDINGS.iter().find_map(|e| word.strip_suffix(e))?;
+
+    // Ab 20 mit "st" ("zwanzigste"), darunter mit "t" ("vierte")
+    if let Some(base) = stem.strip_suffix("st") {
+        if base == "er" {
+            return Some(1);
+        }
+        if let Some(n) = parse_integer_word(base).filter(|n| *n >= 20) {
+            return Some(n);
+        }
+    }
+    let base = stem.strip_suffix('t')?;
+    match base {
+        "drit" => Some(3),
+        "sieb" => Some(7),
+        "ach" => Some(8),
+        _ => parse_integer_word(base).filter(|n| (2..20).contains(n)),
+    }
+}
+
+/// Zerlegt in Wörter; "3km" wird zu "3" und "km"
+pub fn tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split_whitespace() {
+        let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        // Punkte gehören zu Zahlen ("3.", "1.000"), nicht zu Wörtern ("Datei.")
+        let keep_dot = word.starts_with(|c: char| c.is_ascii_digit());
+        let word = word
+            .trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '°' || keep_dot && c == '.'))
+            .to_lowercase();
+        let split = word
+            .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.'))
+            .filter(|&i| i > 0 && word.starts_with(|c: char| c.is_ascii_digit()));
+        match split {
+            Some(i) => {
+                tokens.push(word[..i].to_string());
+                tokens.push(word[i..].to_string());
+            }
+            None if !word.is_empty() => tokens.push(word),
+            None => {}
+        }
+    }
+    tokens
+}
+
+/// Menge am Anfang von `tokens`: (Menge, verbrauchte Wörter)
+pub fn quantity_at(tokens: &[String]) -> Option<(Quantity, usize)> {
+    let first = tokens.first()?.as_str();
+    let word = |i: usize| tokens.get(i).map(String::as_str);
+    let is_article = ARTICLES.contains(&first);
+
+    let (mut value, mut used, ordinal) = if is_article {
+        // "eine halbe Stunde", "ein Kilometer"
+        match word(1).and_then(parse_fraction) {
+            Some(f) => (f, 2, false),
+            None => (1.0, 1, false),
+        }
+    } else if let Some(n) = parse_cardinal(first) {
+        (n, 1, false)
+    } else {
+        let n = parse_ordinal(first)?;
+        return Some((Quantity { value: n as f64, unit: None, ordinal: true }, 1));
+    };
+
+    // "zwei komma fünf"
+    if word(used) == Some("komma") {
+        // Ziffern behalten führende Nullen ("komma 05")
+        let decimals = word(used + 1).and_then(|w| match w.chars().all(|c| c.is_ascii_digit()) {
+            true => Some((w.parse::<u64>().ok()?, w.len())),
+            false => parse_integer_word(w).map(|n| (n, n.to_string().len())),
+        });
+        if let Some((decimals, places)) = decimals {
+            value += decimals as f64 / 10f64.powi(places as i32);
+            used += 2;
+        }
+    }
+
+    let unit = word(used).and_then(Unit::parse);
+    if unit.is_some() {
+        used += 1;
+    } else if is_article {
+        return None;
+    }
+
+    Some((Quantity { value, unit, ordinal }, used))
+}
+
+/// Alle Zahlen und Mengen in einem Text, in Reihenfolge
+pub fn parse_quantities(text: &str) -> Vec<Quantity> {
+    let tokens = tokens(text);
+    let mut quantities = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match quantity_at(&tokens[i..]) {
+            Some((quantity, used)) => {
+                quantities.push(quantity);
+                i += used;
+            }
+            None => i += 1,
+        }
+    }
+    quantities
+}
+
+/// Zieleinheit einer Umrechnung: "in Kilometer", "nach Fahrenheit" oder
+/// nur die Einheit (Antwort auf eine Rückfrage)
+pub fn target_unit(text: &str) -> Option<Unit> {
+    let tokens = tokens(text);
+    if let [word] = tokens.as_slice() {
+        return Unit::parse(word);
+    }
+    tokens
+        .windows(2)
+        .rev()
+        .find(|pair| matches!(pair[0].as_str(), "in" | "nach"))
+        .and_then(|pair| Unit::parse(&pair[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantity(value: f64, unit: Option<Unit>) -> Quantity {
+        Quantity { value, unit, ordinal: false }
+    }
+
+    #[test]
+    fn test_integer_words() {
+        let cases = [
+            ("null", 0), ("eins", 1), ("zwölf", 12), ("siebzehn", 17), ("zwanzig", 20),
+            ("einundzwanzig", 21), ("dreiundzwanzig", 23), ("dreißig", 30), ("neunundneunzig", 99),
+            ("hundert", 100), ("einhundert", 100), ("hundertfünf", 105),
+            ("zweihundertvierundfünfzig", 254), ("tausend", 1000), ("eintausendeins", 1001),
+            ("zweitausendachtzehn", 2018), ("dreihunderttausend", 300_000),
+            ("neunhundertneunundneunzigtausendneunhundertneunundneunzig", 999_999),
+        ];
+        for (word, n) in cases {
+            assert_eq!(parse_integer_word(word), Some(n), "{}", word);
+        }
+
+        for word in ["", "und", "zwanzigund", "elfundzwanzig", "hallo", "nullundzwanzig"] {
+            assert_eq!(parse_integer_word(word), None, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_fractions_and_digits() {
+        assert_eq!(parse_cardinal("halb"), Some(0.5));
+        assert_eq!(parse_cardinal("anderthalb"), Some(1.5));
+        assert_eq!(parse_cardinal("zweieinhalb"), Some(2.5));
+        assert_eq!(parse_cardinal("dreiviertel"), Some(0.75));
+        assert_eq!(parse_cardinal("3,5"), Some(3.5));
+        assert_eq!(parse_cardinal("1.000"), Some(1000.0));
+        assert_eq!(parse_cardinal("1.000,25"), Some(1000.25));
+        assert_eq!(parse_cardinal("2.5"), Some(2.5));
+        assert_eq!(parse_cardinal("Wetter"), None);
+    }
+
+    #[test]
+    fn test_ordinals() {
+        let cases = [
+            ("erste", 1), ("ersten", 1), ("zweiter", 2), ("dritte", 3), ("vierten", 4),
+            ("siebte", 7), ("achte", 8), ("neunzehnte", 19), ("zwanzigste", 20),
+            ("einundzwanzigsten", 21), ("hundertste", 100), ("3.", 3),
+        ];
+        for (word, n) in cases {
+            assert_eq!(parse_ordinal(word), Some(n), "{}", word);
+        }
+        for word in ["test", "gestern", "beste", "liste", "zweite_"] {
+            assert_eq!(parse_ordinal(word), None, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_quantities() {
+        assert_eq!(parse_quantities("erinnere mich in zwanzig Minuten"), vec![quantity(20.0, Some(Unit::Minute))]);
+        assert_eq!(parse_quantities("fünf Meilen in Kilometer"), vec![quantity(5.0, Some(Unit::Mile))]);
+        assert_eq!(parse_quantities("in einer halben Stunde"), vec![quantity(0.5, Some(Unit::Hour))]);
+        assert_eq!(parse_quantities("3km und 2,5 kg"), vec![quantity(3.0, Some(Unit::Kilometer)), quantity(2.5, Some(Unit::Kilogram))]);
+        assert_eq!(parse_quantities("zwei komma fünf Liter"), vec![quantity(2.5, Some(Unit::Liter))]);
+        assert_eq!(parse_quantities("öffne die dritte Datei"), vec![Quantity { value: 3.0, unit: None, ordinal: true }]);
+        // Artikel ohne Einheit sind keine Zahl
+        assert!(parse_quantities("öffne eine Datei").is_empty());
+    }
+
+    #[test]
+    fn test_conversion() {
+        let miles = quantity(5.0, Some(Unit::Mile));
+        let km = miles.convert(Unit::Kilometer).unwrap();
+        assert!((km.value - 8.04672).abs() < 1e-9);
+        assert_eq!(miles.convert(Unit::Kilogram), None);
+
+        let f = quantity(100.0, Some(Unit::Celsius)).convert(Unit::Fahrenheit).unwrap();
+        assert!((f.value - 212.0).abs() < 1e-9);
+        assert_eq!(quantity(0.5, Some(Unit::Hour)).seconds(), Some(1800.0));
+
+        assert_eq!(km.to_string(), "8,05 km");
+        assert_eq!(f.to_string(), "212 °F");
+        assert_eq!(quantity(2.5, None).to_string(), "2,5");
+    }
+
+    #[test]
+    fn test_target_unit() {
+        assert_eq!(target_unit("fünf Meilen in Kilometer"), Some(Unit::Kilometer));
+        assert_eq!(target_unit("rechne 20 Grad nach Fahrenheit um"), Some(Unit::Fahrenheit));
+        assert_eq!(target_unit("Kilo."), Some(Unit::Kilogram));
+        // Die Einheit der Menge ist kein Ziel
+        assert_eq!(target_unit("erinnere mich in zwanzig Minuten"), None);
+        assert_eq!(target_unit("fünf Meilen"), None);
+    }
+}
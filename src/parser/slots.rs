@@ -0,0 +1,239 @@
+use crate::intent::Intent;
+use super::{extract_location, file_target, search_terms};
+use super::datetime::{self, DateRange};
+use super::gazetteer::Gazetteer;
+use super::number::{self, Quantity, Unit};
+use chrono::Local;
+use std::collections::HashMap;
+
+/// Art eines Slots, den ein Intent aus der Äußerung braucht
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlotKind {
+    Location,
+    DateTime,
+    Query,
+    Path,
+    Application,
+    Number,
+    /// Zieleinheit einer Umrechnung
+    Unit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotValue {
+    Text(String),
+    DateTime(DateRange),
+    Quantity(Quantity),
+    Unit(Unit),
+}
+
+/// Deklaration eines Slots durch einen Intent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotSpec {
+    pub kind: SlotKind,
+    pub required: bool,
+    /// Ein gefüllter Alternativ-Slot erfüllt die Pflicht ebenfalls
+    /// ("starte firefox" braucht keinen Pfad)
+    pub alternative: Option<SlotKind>,
+    /// Rückfrage, wenn der Slot fehlt
+    pub question: &'static str,
+}
+
+impl SlotSpec {
+    pub const fn required(kind: SlotKind, question: &'static str) -> Self {
+        Self { kind, required: true, alternative: None, question }
+    }
+
+    pub const fn optional(kind: SlotKind) -> Self {
+        Self { kind, required: false, alternative: None, question: "" }
+    }
+
+    pub const fn or(self, alternative: SlotKind) -> Self {
+        Self { alternative: Some(alternative), ..self }
+    }
+}
+
+/// Gefüllte Slots einer Äußerung
+#[derive(Debug, Clone, Default)]
+pub struct Slots {
+    values: HashMap<SlotKind, SlotValue>,
+}
+
+impl Slots {
+    pub fn get(&self, kind: SlotKind) -> Option<&SlotValue> {
+        self.values.get(&kind)
+    }
+
+    pub fn text(&self, kind: SlotKind) -> Option<&str> {
+        match self.get(kind)? {
+            SlotValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn date_range(&self) -> Option<DateRange> {
+        match self.get(SlotKind::DateTime)? {
+            SlotValue::DateTime(range) => Some(*range),
+            _ => None,
+        }
+    }
+
+    pub fn quantity(&self) -> Option<Quantity> {
+        match self.get(SlotKind::Number)? {
+            SlotValue::Quantity(q) => Some(*q),
+            _ => None,
+        }
+    }
+
+    pub fn unit(&self) -> Option<Unit> {
+        match self.get(SlotKind::Unit)? {
+            SlotValue::Unit(unit) => Some(*unit),
+            _ => None,
+        }
+    }
+
+    /// Pflicht-Slots des Intents, die noch fehlen, in Deklarationsreihenfolge
+    pub fn missing(&self, intent: &Intent) -> Vec<&'static SlotSpec> {
+        intent
+            .slots()
+            .iter()
+            .filter(|spec| spec.required)
+            .filter(|spec| !self.values.contains_key(&spec.kind))
+            .filter(|spec| spec.alternative.is_none_or(|alt| !self.values.contains_key(&alt)))
+            .collect()
+    }
+
+    /// Füllt einen Slot aus der Antwort auf eine Rückfrage. Findet der
+    /// Extraktor nichts ("Berlin" ohne "in"), zählt bei Text-Slots die
+    /// ganze Antwort.
+    pub fn answer(&mut self, kind: SlotKind, text: &str) {
+        let value = extract(kind, text).or_else(|| {
+            let text = text.trim().trim_end_matches(['?', '.', '!']).trim();
+            let is_text = !matches!(kind, SlotKind::DateTime | SlotKind::Number | SlotKind::Unit);
+            (is_text && !text.is_empty()).then(|| SlotValue::Text(text.to_string()))
+        });
+
+        if let Some(value) = value {
+            self.values.insert(kind, value);
+        }
+    }
+}
+
+/// Füllt alle Slots, die der Intent deklariert
+pub fn extract_slots(intent: &Intent, text: &str) -> Slots {
+    let values = intent
+        .slots()
+        .iter()
+        .filter_map(|spec| Some((spec.kind, extract(spec.kind, text)?)))
+        .collect();
+
+    Slots { values }
+}
+
+fn extract(kind: SlotKind, text: &str) -> Option<SlotValue> {
+    match kind {
+        SlotKind::Location => location(text).map(SlotValue::Text),
+        SlotKind::DateTime => datetime::parse_datetime(text, Local::now().naive_local()).map(SlotValue::DateTime),
+        SlotKind::Query => search_terms(text).map(SlotValue::Text),
+        SlotKind::Path => match file_target(text)? {
+            ("starte" | "start", _) => None,
+            (_, target) => (!target.is_empty()).then_some(SlotValue::Text(target)),
+        },
+        SlotKind::Application => match file_target(text)? {
+            ("starte" | "start", target) => (!target.is_empty()).then_some(SlotValue::Text(target)),
+            _ => None,
+        },
+        SlotKind::Number => number::parse_quantities(text).into_iter().next().map(SlotValue::Quantity),
+        SlotKind::Unit => number::target_unit(text).map(SlotValue::Unit),
+    }
+}
+
+/// Bekannter Ort aus dem Ortsverzeichnis, sonst der Ort hinter einer
+/// Präposition ohne angehängte Zeit- und Mengenangaben ("in Kleinkleckersdorf
+/// morgen" → "Kleinkleckersdorf", "für drei Tage" → kein Ort)
+fn location(text: &str) -> Option<String> {
+    if let Some(place) = Gazetteer::bundled().find(text) {
+        return Some(place.name.clone());
+    }
+    let found = extract_location(text)?;
+    let place: Vec<&str> = found
+        .split_whitespace()
+        .take_while(|w| !datetime::is_time_word(w) && number::parse_cardinal(w).is_none())
+        .collect();
+
+    (!place.is_empty()).then(|| place.join(" "))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_slots() {
+        let slots = extract_slots(&Intent::Weather, "Wie wird das Wetter in Jena morgen?");
+        assert_eq!(slots.text(SlotKind::Location), Some("Jena"));
+        let tomorrow = Local::now().date_naive().succ_opt().unwrap();
+        assert_eq!(slots.date_range().map(|r| r.start.date()), Some(tomorrow));
+        assert!(slots.missing(&Intent::Weather).is_empty());
+
+        let slots = extract_slots(&Intent::Weather, "Wetter Müchen übermorgen");
+        assert_eq!(slots.text(SlotKind::Location), Some("München"));
+
+        let slots = extract_slots(&Intent::Weather, "Wetter für drei Tage");
+        assert_eq!(slots.date_range().map(|r| r.day_count()), Some(3));
+        assert_eq!(slots.quantity().and_then(|q| q.unit), Some(Unit::Day));
+        assert_eq!(slots.text(SlotKind::Location), None);
+        assert_eq!(slots.missing(&Intent::Weather)[0].kind, SlotKind::Location);
+
+        // Andere Zahlen füllen nur den Zahl-Slot, keinen Zeitraum
+        let slots = extract_slots(&Intent::Weather, "Wetter in Berlin bei 25 Grad");
+        assert_eq!(slots.date_range(), None);
+        assert_eq!(slots.quantity().map(|q| q.value), Some(25.0));
+
+        // Zu weit in der Zukunft: kein Zeitraum
+        let slots = extract_slots(&Intent::Weather, "Wetter in 99999999999 Wochen");
+        assert_eq!(slots.date_range(), None);
+    }
+
+    #[test]
+    fn test_convert_slots() {
+        let slots = extract_slots(&Intent::Convert, "Wie viel sind fünf Meilen in Kilometer?");
+        assert_eq!(slots.quantity(), Some(Quantity { value: 5.0, unit: Some(Unit::Mile), ordinal: false }));
+        assert_eq!(slots.unit(), Some(Unit::Kilometer));
+        assert!(slots.missing(&Intent::Convert).is_empty());
+
+        let mut slots = extract_slots(&Intent::Convert, "rechne 3 Pfund um");
+        assert_eq!(slots.missing(&Intent::Convert)[0].kind, SlotKind::Unit);
+        slots.answer(SlotKind::Unit, "Kilo");
+        assert_eq!(slots.unit(), Some(Unit::Kilogram));
+        // Keine Einheit: bleibt leer statt Text
+        slots.answer(SlotKind::Unit, "weiß nicht");
+        assert_eq!(slots.unit(), Some(Unit::Kilogram));
+    }
+
+    #[test]
+    fn test_missing_and_answer() {
+        let mut slots = extract_slots(&Intent::Search, "suche");
+        assert_eq!(slots.missing(&Intent::Search)[0].question, "Wonach soll ich suchen?");
+
+        slots.answer(SlotKind::Query, "Rust Programmierung");
+        assert_eq!(slots.text(SlotKind::Query), Some("rust programmierung"));
+        assert!(slots.missing(&Intent::Search).is_empty());
+
+        let mut slots = Slots::default();
+        slots.answer(SlotKind::Location, "in Berlin.");
+        assert_eq!(slots.text(SlotKind::Location), Some("Berlin"));
+    }
+
+    #[test]
+    fn test_application_satisfies_path() {
+        let slots = extract_slots(&Intent::FileOpen, "starte firefox");
+        assert_eq!(slots.text(SlotKind::Application), Some("firefox"));
+        assert_eq!(slots.text(SlotKind::Path), None);
+        assert!(slots.missing(&Intent::FileOpen).is_empty());
+
+        let slots = extract_slots(&Intent::FileOpen, "öffne");
+        assert_eq!(slots.missing(&Intent::FileOpen).len(), 1);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::intent::Intent;
+use crate::classifier::{TrainingExample, TrainingSet};
+use crate::learning::{LearnedSample, LearningStore};
+use crate::vector::DocStats;
+
+/// Statische Beispielphrasen: (Intent, Phrase, Gewicht)
+pub const STATIC_SAMPLES: &[(Intent, &str, f32)] = &[
+    (Intent::Greeting, "hallo hi hey guten tag morgen servus grüß", 1.0),
+    (Intent::Weather, "wetter temperatur grad celsius vorhersage klima regnet schneit scheint sonne", 1.0),
+    (Intent::Search, "suche finde informationen recherchiere was ist erkläre über wiki wissen", 1.2),
+    (Intent::FileOpen, "öffne starte start öffnen datei programm anwendung app ausführen", 1.0),
+    (Intent::FileShow, "zeige zeig anzeigen wo ist finde datei ordner mir", 1.2),  // Erhöht
+    (Intent::Convert, "rechne umrechnen umrechnung meilen kilometer meter zoll pfund kilo liter fahrenheit", 1.0),
+    (Intent::Goodbye, "tschüss danke bis später wiedersehen auf wiedersehen", 1.0),
+    (Intent::Shutdown, "schönen tag noch abmelden beenden ausschalten ende", 1.0),
+];
+
+/// Trainingsdaten aus statischen Phrasen und gelernten Beispielen.
+/// Muss nach jeder Änderung am LearningStore neu erstellt werden.
+pub fn training_set(learning: &LearningStore) -> TrainingSet {
+    training_set_from(&learning.samples, &learning.doc_stats)
+}
+
+/// Index in `LearningStore::samples` zu einem Beispielindex des
+/// Klassifizierers (`IntentScore::sample_index`). Statische Phrasen stehen
+/// dort vorne.
+pub fn learned_index(learning: &LearningStore, sample_index: usize) -> Option<usize> {
+    sample_index
+        .checked_sub(STATIC_SAMPLES.len())
+        .filter(|&i| i < learning.samples.len())
+}
+
+/// Wie `training_set`, aber mit einer Auswahl gelernter Beispiele
+/// (z.B. für die Kreuzvalidierung)
+pub fn training_set_from(
+    learned: &[LearnedSample],
+    learned_stats: &DocStats,
+) -> TrainingSet {
+    let mut doc_stats = learned_stats.clone();
+    let mut examples = Vec::new();
+
+    for (intent, phrase, weight) in STATIC_SAMPLES {
+        doc_stats.add_document(phrase);
+        examples.push(TrainingExample {
+            intent: intent.clone(),
+            phrase: phrase.to_string(),
+            weight: *weight,
+        });
+    }
+
+    for s in learned {
+        examples.push(TrainingExample {
+            intent: s.intent.clone(),
+            phrase: s.phrase.clone(),
+            weight: s.weight,
+        });
+    }
+
+    TrainingSet { examples, doc_stats }
+}
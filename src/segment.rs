@@ -0,0 +1,149 @@
+use crate::intent::Intent;
+use crate::classifier::{Classifier, ClassificationResult};
+use crate::boost::BoostRules;
+use crate::thresholds::Thresholds;
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Satzzeichen bleiben am Teilsatz ("?" zählt für die Boost-Regeln),
+/// Konjunktionen werden entfernt. Punkte nur vor Leerzeichen oder am
+/// Ende, damit Dateinamen ("bericht.pdf") erhalten bleiben.
+fn boundary() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(?P<punct>[,;!?]|\.(?:\s|$))|\b(?:und|dann|danach|anschließend|außerdem|sowie)\b")
+            .expect("Segmentierungs-Regex ist gültig")
+    })
+}
+
+/// Bereiche der Teilsätze im Originaltext
+fn clause_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+
+    for caps in boundary().captures_iter(text) {
+        let m = caps.get(0).expect("Gesamttreffer existiert");
+        let end = caps.name("punct").map_or(m.start(), |p| p.start() + 1);
+        spans.push(start..end);
+        start = m.end();
+    }
+    spans.push(start..text.len());
+
+    spans
+        .into_iter()
+        .filter_map(|span| trim_span(text, span))
+        .collect()
+}
+
+/// Entfernt Leerraum und Kommas/Semikolons/Punkte an den Rändern
+fn trim_span(text: &str, span: Range<usize>) -> Option<Range<usize>> {
+    let part = &text[span.clone()];
+    let start = span.start + (part.len() - part.trim_start().len());
+    let end = span.start + part.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '.')).len();
+    (start < end).then_some(start..end)
+}
+
+/// Zerlegt eine Äußerung an Satzzeichen und Konjunktionen (für Tests;
+/// das Programm arbeitet mit den Bereichen aus `clause_spans`)
+#[cfg(test)]
+pub fn split_clauses(text: &str) -> Vec<&str> {
+    clause_spans(text).into_iter().map(|s| &text[s]).collect()
+}
+
+/// Ein klassifizierter Teilsatz
+#[derive(Clone)]
+pub struct Clause {
+    pub text: String,
+    pub result: ClassificationResult,
+    pub intent: Intent,
+    span: Range<usize>,
+}
+
+/// Klassifiziert jeden Teilsatz einzeln, in Reihenfolge.
+///
+/// Unsichere Teilsätze sind meist keine eigenen Befehle ("Wetter in Berlin
+/// und Hamburg"); sie werden an den vorherigen Teilsatz angehängt bzw. dem
+/// nächsten vorangestellt und zusammen neu klassifiziert.
+pub fn classify_clauses(
+    classifier: &mut dyn Classifier,
+    rules: &BoostRules,
+    thresholds: &Thresholds,
+    text: &str,
+) -> Vec<Clause> {
+    let mut classify = |span: Range<usize>| {
+        let clause_text = &text[span.clone()];
+        let mut result = classifier.predict(clause_text);
+        rules.apply(&mut result, clause_text);
+        Clause {
+            text: clause_text.to_string(),
+            intent: thresholds.decide(&result),
+            result,
+            span,
+        }
+    };
+
+    let mut clauses: Vec<Clause> = Vec::new();
+    let mut pending: Option<usize> = None;
+
+    for span in clause_spans(text) {
+        let start = pending.take().unwrap_or(span.start);
+        let clause = classify(start..span.end);
+
+        if clause.intent != Intent::Unknown {
+            clauses.push(clause);
+        } else if let Some(prev) = clauses.pop() {
+            clauses.push(classify(prev.span.start..span.end));
+        } else {
+            pending = Some(start);
+        }
+    }
+
+    // Nur unsichere Teile: als Ganzes melden
+    if let Some(span) = pending.and_then(|start| trim_span(text, start..text.len())) {
+        clauses.push(classify(span));
+    }
+
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bayes::NaiveBayes;
+    use crate::learning::LearningStore;
+    use crate::samples;
+
+    #[test]
+    fn test_split_clauses() {
+        assert_eq!(
+            split_clauses("Hallo, wie ist das Wetter in Berlin und öffne meinen Bericht"),
+            vec!["Hallo", "wie ist das Wetter in Berlin", "öffne meinen Bericht"]
+        );
+        assert_eq!(split_clauses("Wie wird das Wetter? Dann zeige bericht.pdf."), vec!["Wie wird das Wetter?", "zeige bericht.pdf"]);
+        assert_eq!(split_clauses("hallo"), vec!["hallo"]);
+        assert!(split_clauses(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_classify_clauses_in_order() {
+        let mut classifier = NaiveBayes::new();
+        classifier.train(&samples::training_set(&LearningStore::new()));
+        let rules = BoostRules::default();
+        let thresholds = Thresholds::default();
+
+        let clauses = classify_clauses(
+            &mut classifier,
+            &rules,
+            &thresholds,
+            "Hallo, das Wetter in Berlin und dann öffne meinen Bericht",
+        );
+        let intents: Vec<Intent> = clauses.iter().map(|c| c.intent.clone()).collect();
+        assert_eq!(intents, vec![Intent::Greeting, Intent::Weather, Intent::FileOpen]);
+
+        // "Hamburg" allein ist kein Befehl und bleibt beim Wetter
+        let clauses = classify_clauses(&mut classifier, &rules, &thresholds, "Wetter in Berlin und Hamburg");
+        assert_eq!(clauses.len(), 1);
+        assert_eq!(clauses[0].text, "Wetter in Berlin und Hamburg");
+    }
+}
@@ -1,4 +1,9 @@
+/// Kosinus-Ähnlichkeit; Vektoren verschiedener Dimension sind unvergleichbar (0)
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+
     let mut dot = 0.0;
     let mut na = 0.0;
     let mut nb = 0.0;
@@ -15,3 +20,21 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 
     dot / (na.sqrt() * nb.sqrt())
 }
+
+/// Levenshtein-Abstand in Zeichen (nicht Bytes)
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,205 @@
+use crate::classifier::TrainingSet;
+use crate::similarity::edit_distance;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
+
+/// Allgemeine Wörter, die nie korrigiert werden (Inhalt von
+/// `data/common_words.txt` beim Bauen). Der Wortschatz besteht nur aus den
+/// Beispielphrasen, ohne diese Liste würde z.B. "nach" zu "noch".
+const COMMON_WORDS: &str = include_str!("../data/common_words.txt");
+
+/// Kürzere Wörter werden nicht korrigiert ("das", "mir" sind zu nah an
+/// zu vielen anderen Wörtern)
+const MIN_LEN: usize = 4;
+
+/// Längere Wörter werden nicht korrigiert: echte Tippfehler sind nie so
+/// lang, und die Zahl der Löschvarianten wächst quadratisch
+const MAX_LEN: usize = 20;
+
+/// Größter Tippabstand; bis 7 Zeichen nur 1 Fehler
+const MAX_DISTANCE: usize = 2;
+
+/// Eine vorgenommene Korrektur ("wettter" → "wetter")
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction {
+    pub from: String,
+    pub to: String,
+}
+
+/// Rechtschreibkorrektur nach SymSpell: Für jedes bekannte Wort werden alle
+/// Varianten mit bis zu `MAX_DISTANCE` gelöschten Zeichen vorab indiziert.
+/// Eine Anfrage erzeugt nur die Löschvarianten des Eingabeworts und prüft
+/// die wenigen Kandidaten, das ist schnell genug für jede Nachricht.
+#[derive(Clone, Default)]
+pub struct SpellChecker {
+    /// Bekannte Wörter mit Häufigkeit
+    words: HashMap<String, u32>,
+    /// Löschvariante → bekannte Wörter
+    deletes: HashMap<String, Vec<String>>,
+}
+
+impl SpellChecker {
+    /// Wortschatz aus allen Trainingsphrasen (statisch und gelernt)
+    pub fn from_training(data: &TrainingSet) -> Self {
+        let mut checker = Self::default();
+        for ex in &data.examples {
+            for word in ex.phrase.split_whitespace() {
+                checker.add_word(&clean(word));
+            }
+        }
+        checker
+    }
+
+    pub fn add_word(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        let count = self.words.entry(word.to_string()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            return;
+        }
+
+        for variant in deletes(word, MAX_DISTANCE) {
+            self.deletes.entry(variant).or_default().push(word.to_string());
+        }
+    }
+
+    /// Bestes bekanntes Wort für ein unbekanntes, `None` wenn das Wort
+    /// bekannt ist oder nichts nah genug liegt
+    pub fn suggest(&self, word: &str) -> Option<&str> {
+        let len = word.chars().count();
+        if !(MIN_LEN..=MAX_LEN).contains(&len)
+            || self.words.contains_key(word)
+            || common_words().contains(word)
+            || word.chars().any(|c| c.is_numeric())
+        {
+            return None;
+        }
+        let max_distance = if len >= 8 { MAX_DISTANCE } else { 1 };
+
+        deletes(word, max_distance)
+            .iter()
+            .filter_map(|variant| self.deletes.get(variant))
+            .flatten()
+            .map(|candidate| (edit_distance(word, candidate), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            // Kleinster Abstand, dann häufigstes Wort, dann alphabetisch
+            .min_by(|a, b| {
+                a.0.cmp(&b.0)
+                    .then(self.words[b.1].cmp(&self.words[a.1]))
+                    .then(a.1.cmp(b.1))
+            })
+            .map(|(_, candidate)| candidate.as_str())
+    }
+
+    /// Korrigiert unbekannte Wörter im Text. Satzzeichen am Wortrand
+    /// bleiben erhalten, korrigierte Wörter sind kleingeschrieben.
+    pub fn correct(&self, text: &str) -> (String, Vec<Correction>) {
+        let mut corrections = Vec::new();
+        let words: Vec<String> = text
+            .split_whitespace()
+            .map(|word| {
+                let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+                match self.suggest(&clean(core)) {
+                    Some(fixed) if !core.is_empty() => {
+                        corrections.push(Correction { from: core.to_string(), to: fixed.to_string() });
+                        word.replacen(core, fixed, 1)
+                    }
+                    _ => word.to_string(),
+                }
+            })
+            .collect();
+
+        (words.join(" "), corrections)
+    }
+}
+
+fn common_words() -> &'static HashSet<String> {
+    static WORDS: OnceLock<HashSet<String>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        COMMON_WORDS
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(clean)
+            .filter(|word| !word.is_empty())
+            .collect()
+    })
+}
+
+/// Vergleichsform eines Wortes: NFC, kleingeschrieben, ohne Satzzeichen
+fn clean(word: &str) -> String {
+    word.nfc()
+        .collect::<String>()
+        .to_lowercase()
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Alle Varianten mit bis zu `max` gelöschten Zeichen (inkl. dem Wort selbst)
+fn deletes(word: &str, max: usize) -> HashSet<String> {
+    let mut all = HashSet::from([word.to_string()]);
+    let mut frontier = vec![word.to_string()];
+
+    for _ in 0..max {
+        let mut next = Vec::new();
+        for w in &frontier {
+            let chars: Vec<char> = w.chars().collect();
+            for i in 0..chars.len() {
+                let variant: String = chars[..i].iter().chain(&chars[i + 1..]).collect();
+                if all.insert(variant.clone()) {
+                    next.push(variant);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> SpellChecker {
+        let mut checker = SpellChecker::default();
+        for word in "wetter öffne datei informationen zeige suche".split(' ') {
+            checker.add_word(word);
+        }
+        checker
+    }
+
+    #[test]
+    fn test_corrects_typos() {
+        let checker = checker();
+        assert_eq!(checker.suggest("wettter"), Some("wetter"));
+        assert_eq!(checker.suggest("öfne"), Some("öffne"));
+        assert_eq!(checker.suggest("informatonen"), Some("informationen"));
+        // Bekannt, zu kurz oder zu weit weg
+        assert_eq!(checker.suggest("wetter"), None);
+        assert_eq!(checker.suggest("dat"), None);
+        assert_eq!(checker.suggest("berlin"), None);
+        assert_eq!(checker.suggest(&"wettter".repeat(30)), None);
+    }
+
+    #[test]
+    fn test_common_words_stay() {
+        let mut checker = checker();
+        for word in ["noch", "über"] {
+            checker.add_word(word);
+        }
+        let (text, corrections) = checker.correct("Nach Berlin, doch aber ober");
+        assert_eq!(text, "Nach Berlin, doch aber ober");
+        assert!(corrections.is_empty());
+        assert_eq!(checker.suggest("nooch"), Some("noch"));
+    }
+
+    #[test]
+    fn test_correct_reports_changes() {
+        let (text, corrections) = checker().correct("Wie ist das Wettter?");
+        assert_eq!(text, "Wie ist das wetter?");
+        assert_eq!(corrections, vec![Correction { from: "Wettter".to_string(), to: "wetter".to_string() }]);
+    }
+}
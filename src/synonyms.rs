@@ -0,0 +1,85 @@
+use crate::normalize::normalize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+/// Mitgelieferte Synonymgruppen (Inhalt von `data/synonyms.txt` beim Bauen)
+const DEFAULT_SYNONYMS: &str = include_str!("../data/synonyms.txt");
+
+/// Synonyme im Textformat des OpenThesaurus-Exports: eine Gruppe pro Zeile,
+/// Begriffe durch ";" getrennt, Anmerkungen wie "(ugs.)" in Klammern.
+/// Gespeichert wird in normalisierter Form (wie `Vocab` die Wörter sieht),
+/// mehrteilige Begriffe ("laufen lassen") werden übergangen.
+#[derive(Clone, Default)]
+pub struct Thesaurus {
+    synonyms: HashMap<String, HashSet<String>>,
+}
+
+impl Thesaurus {
+    /// Die mitgelieferten Gruppen
+    pub fn bundled() -> Self {
+        let mut thesaurus = Self::default();
+        thesaurus.add_text(DEFAULT_SYNONYMS);
+        thesaurus
+    }
+
+    /// Ergänzt die Gruppen aus einer Datei (z.B. vollständiger
+    /// OpenThesaurus-Export)
+    pub fn load_file(&mut self, path: &str) -> Result<(), String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Synonyme '{}' nicht lesbar: {}", path, e))?;
+        self.add_text(&text);
+        Ok(())
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        for line in text.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let terms: Vec<String> = line
+                .split(';')
+                .map(|term| normalize(&strip_annotations(term)))
+                .filter(|term| !term.is_empty() && !term.contains(' '))
+                .collect();
+
+            for term in &terms {
+                let entry = self.synonyms.entry(term.clone()).or_default();
+                entry.extend(terms.iter().filter(|t| *t != term).cloned());
+            }
+        }
+    }
+
+    /// Synonyme eines normalisierten Wortes
+    pub fn synonyms(&self, word: &str) -> impl Iterator<Item = &String> {
+        self.synonyms.get(word).into_iter().flatten()
+    }
+}
+
+/// Entfernt Anmerkungen in Klammern: "(ugs.) tschö" → "tschö"
+fn strip_annotations(term: &str) -> String {
+    let mut depth = 0usize;
+    term.chars()
+        .filter(|&c| match c {
+            '(' => { depth += 1; false }
+            ')' => { depth = depth.saturating_sub(1); false }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openthesaurus_lines() {
+        let mut thesaurus = Thesaurus::default();
+        thesaurus.add_text("# Kommentar\nRegen;Niederschlag;(ugs.) Schiffen;Landregen von oben\n");
+
+        let syn: HashSet<&String> = thesaurus.synonyms(&normalize("Niederschlag")).collect();
+        assert!(syn.contains(&normalize("Regen")));
+        assert!(syn.contains(&normalize("schiffen")));
+        assert!(!syn.contains(&normalize("Niederschlag")));
+        assert_eq!(thesaurus.synonyms("landregen von oben").count(), 0);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::intent::Intent;
+use crate::classifier::ClassificationResult;
+use crate::config::Config;
+use crate::eval::{self, Prediction};
+use crate::learning::LearningStore;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::fs;
+
+pub const FILE: &str = "thresholds.json";
+
+/// Mindest-Score, unter dem ein Ergebnis als unsicher gilt
+pub const MIN_SCORE: f32 = 0.5;
+
+/// Schwellen für einen Intent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IntentThreshold {
+    pub min_score: f32,
+    /// Mindestabstand zum zweitbesten Intent
+    #[serde(default)]
+    pub margin: f32,
+}
+
+/// Entscheidet, ob der beste Kandidat sicher genug ist. Intents ohne
+/// eigenen Eintrag nutzen `default`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    pub default: IntentThreshold,
+    pub per_intent: HashMap<Intent, IntentThreshold>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        let mut per_intent = HashMap::new();
+        // Beenden lässt sich nicht rückgängig machen
+        per_intent.insert(Intent::Shutdown, IntentThreshold { min_score: 0.8, margin: 0.15 });
+
+        Self {
+            default: IntentThreshold { min_score: MIN_SCORE, margin: 0.0 },
+            per_intent,
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn load() -> Self {
+        match fs::read_to_string(FILE) {
+            Ok(data) => match serde_json::from_str(&data) {
+                Ok(thresholds) => thresholds,
+                Err(e) => {
+                    eprintln!("Fehler in {}: {} – nutze Standardschwellen.", FILE, e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(FILE, json)
+    }
+
+    pub fn get(&self, intent: &Intent) -> IntentThreshold {
+        self.per_intent.get(intent).copied().unwrap_or(self.default)
+    }
+
+    pub fn is_uncertain(&self, result: &ClassificationResult) -> bool {
+        match result.best() {
+            Some(best) => {
+                let t = self.get(&best.intent);
+                best.score < t.min_score || result.margin() < t.margin
+            }
+            None => true,
+        }
+    }
+
+    /// Gewählter Intent, oder `Intent::Unknown` wenn unsicher
+    pub fn decide(&self, result: &ClassificationResult) -> Intent {
+        match result.best() {
+            Some(best) if !self.is_uncertain(result) => best.intent.clone(),
+            _ => Intent::Unknown,
+        }
+    }
+
+    /// Wählt Schwellen pro Intent anhand gelabelter Vorhersagen.
+    ///
+    /// Für jeden Intent werden die Äußerungen betrachtet, bei denen er vorne
+    /// lag. Annehmen ist richtig, wenn er erwartet war; Ablehnen ist richtig,
+    /// wenn nicht (Rückfrage ist besser als eine falsche Aktion). Gewählt
+    /// wird der Schnitt mit den meisten richtigen Entscheidungen, erst für
+    /// den Score, dann für den Abstand zum Zweitbesten.
+    pub fn calibrate(&self, predictions: &[Prediction]) -> Thresholds {
+        let mut calibrated = self.clone();
+
+        for intent in Intent::ALL {
+            let led: Vec<&Prediction> = predictions
+                .iter()
+                .filter(|p| p.result.best().is_some_and(|b| b.intent == intent))
+                .collect();
+            if led.is_empty() {
+                continue;
+            }
+
+            let current = self.get(&intent);
+            let scores: Vec<(f32, bool)> = led
+                .iter()
+                .map(|p| (p.score(), p.expected == intent))
+                .collect();
+            let min_score = best_cut(&scores, current.min_score);
+
+            let margins: Vec<(f32, bool)> = led
+                .iter()
+                .filter(|p| p.score() >= min_score)
+                .map(|p| (p.result.margin(), p.expected == intent))
+                .collect();
+            let margin = best_cut(&margins, current.margin);
+
+            calibrated.per_intent.insert(intent, IntentThreshold { min_score, margin });
+        }
+
+        calibrated
+    }
+}
+
+/// Kommandozeile: `calibrate [korpus.json]`. Schreibt die kalibrierten
+/// Schwellen nach `thresholds.json`.
+pub fn run(args: &[String]) {
+    let corpus_path = args.first().map_or(eval::DEFAULT_CORPUS, String::as_str);
+
+    let config = Config::load();
+    let learning = LearningStore::load();
+    let current = Thresholds::load();
+    let eval::Pipeline { mut classifier, rules, corpus } = match eval::load_pipeline(&config, &learning, corpus_path) {
+        Ok(pipeline) => pipeline,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    let before = eval::evaluate(classifier.as_mut(), &rules, &current, &corpus);
+    let calibrated = current.calibrate(&before.predictions);
+    let after = eval::evaluate(classifier.as_mut(), &rules, &calibrated, &corpus);
+
+    println!("=== {} auf {} ({} Äußerungen) ===\n", classifier.name(), corpus_path, corpus.len());
+    println!("{:<10} {:>17} {:>17}", "Intent", "Score vorher/neu", "Abstand vorher/neu");
+    for intent in Intent::ALL {
+        let (old, new) = (current.get(&intent), calibrated.get(&intent));
+        println!("{:<10} {:>8.3}/{:<8.3} {:>8.3}/{:<8.3}", format!("{:?}", intent), old.min_score, new.min_score, old.margin, new.margin);
+    }
+    // Kalibriert und gemessen auf demselben Korpus, daher optimistisch
+    println!("\nGenauigkeit: {:.1}% → {:.1}%", before.accuracy() * 100.0, after.accuracy() * 100.0);
+
+    match calibrated.save() {
+        Ok(()) => println!("Schwellen nach {} geschrieben.", FILE),
+        Err(e) => eprintln!("{} konnte nicht geschrieben werden: {}", FILE, e),
+    }
+}
+
+/// Bester Schnitt über (Wert, annehmen_richtig): Werte ≥ Schnitt werden
+/// angenommen. Werden alle angenommen, bleibt es höchstens bei `current`.
+fn best_cut(values: &[(f32, bool)], current: f32) -> f32 {
+    if values.is_empty() {
+        return current;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Schnitt j: sorted[..j] abgelehnt, sorted[j..] angenommen
+    let mut utility: usize = sorted.iter().filter(|v| v.1).count();
+    let (mut best_j, mut best_utility) = (0, utility);
+    for j in 1..=sorted.len() {
+        if sorted[j - 1].1 {
+            utility -= 1;
+        } else {
+            utility += 1;
+        }
+        if utility > best_utility {
+            best_j = j;
+            best_utility = utility;
+        }
+    }
+
+    match best_j {
+        0 => current.min(sorted[0].0),
+        j if j == sorted.len() => sorted[j - 1].0 + 0.01,
+        j => (sorted[j - 1].0 + sorted[j].0) / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classifier::IntentScore;
+
+    fn result(scores: &[(Intent, f32)]) -> ClassificationResult {
+        ClassificationResult::ranked(
+            scores
+                .iter()
+                .map(|(intent, score)| IntentScore {
+                    intent: intent.clone(),
+                    similarity: *score,
+                    weight: 1.0,
+                    boost: 1.0,
+                    score: *score,
+                    sample_index: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn prediction(expected: Intent, scores: &[(Intent, f32)]) -> Prediction {
+        let result = result(scores);
+        Prediction {
+            text: String::new(),
+            predicted: result.best().unwrap().intent.clone(),
+            expected,
+            result,
+        }
+    }
+
+    #[test]
+    fn test_per_intent_threshold_and_margin() {
+        let t = Thresholds::default();
+
+        assert_eq!(t.decide(&result(&[(Intent::Greeting, 0.6)])), Intent::Greeting);
+        assert_eq!(t.decide(&result(&[(Intent::Shutdown, 0.6)])), Intent::Unknown);
+        // Hoher Score, aber zu knapp vor dem Zweitbesten
+        let close = result(&[(Intent::Shutdown, 0.9), (Intent::Goodbye, 0.85)]);
+        assert!(t.is_uncertain(&close));
+        assert_eq!(t.decide(&result(&[(Intent::Shutdown, 0.9), (Intent::Goodbye, 0.5)])), Intent::Shutdown);
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let predictions = vec![
+            prediction(Intent::Weather, &[(Intent::Weather, 0.9)]),
+            prediction(Intent::Weather, &[(Intent::Weather, 0.7)]),
+            prediction(Intent::Unknown, &[(Intent::Weather, 0.55)]),
+            prediction(Intent::Search, &[(Intent::Weather, 0.52)]),
+            prediction(Intent::Greeting, &[(Intent::Greeting, 0.4)]),
+        ];
+
+        let t = Thresholds::default().calibrate(&predictions);
+        let weather = t.get(&Intent::Weather);
+        assert!(weather.min_score > 0.55 && weather.min_score < 0.7);
+        // Alle Begrüßungen waren richtig: Schwelle sinkt bis zum niedrigsten Score
+        assert_eq!(t.get(&Intent::Greeting).min_score, 0.4);
+        // Ohne Daten bleibt die Standardschwelle
+        assert_eq!(t.get(&Intent::Shutdown), Thresholds::default().get(&Intent::Shutdown));
+    }
+}
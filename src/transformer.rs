@@ -0,0 +1,217 @@
+use crate::embeddings::unit;
+use crate::vector::SentenceEmbedder;
+use std::path::Path;
+use tokenizers::Tokenizer;
+use tract_onnx::prelude::*;
+
+/// Eingaben werden auf diese Länge gekürzt bzw. mit Padding aufgefüllt.
+/// So wird das Modell nur einmal für eine feste Form optimiert.
+const MAX_TOKENS: usize = 128;
+
+/// Modelleingaben, wie sie Exporte von Hugging Face benennen
+#[derive(Debug, Clone, Copy)]
+enum Input {
+    Ids,
+    Mask,
+    TypeIds,
+}
+
+/// Satzvektoren aus einem kleinen Transformer (MiniLM o.ä.) im ONNX-Format,
+/// auf der CPU mit `tract` ausgeführt. Gibt das Modell Token-Vektoren aus
+/// (`last_hidden_state`), wird über die echten Tokens gemittelt (Mean
+/// Pooling wie bei sentence-transformers).
+pub struct SentenceEncoder {
+    plan: TypedRunnableModel<TypedModel>,
+    tokenizer: Tokenizer,
+    /// Eingaben in der Reihenfolge des Modells
+    inputs: Vec<Input>,
+    dim: usize,
+}
+
+impl SentenceEncoder {
+    /// Lädt `model.onnx` und `tokenizer.json` aus einem Verzeichnis
+    pub fn load(dir: &str) -> Result<Self, String> {
+        let dir = Path::new(dir);
+        let tokenizer = Tokenizer::from_file(dir.join("tokenizer.json"))
+            .map_err(|e| format!("Tokenizer in '{}': {}", dir.display(), e))?;
+        let model = tract_onnx::onnx()
+            .model_for_path(dir.join("model.onnx"))
+            .map_err(|e| format!("Modell in '{}': {}", dir.display(), e))?;
+
+        Self::from_model(model, tokenizer)
+    }
+
+    pub fn from_model(mut model: InferenceModel, tokenizer: Tokenizer) -> Result<Self, String> {
+        let error = |e: TractError| format!("Modell nicht nutzbar: {}", e);
+
+        let mut inputs = Vec::new();
+        for (i, outlet) in model.input_outlets().map_err(error)?.to_vec().into_iter().enumerate() {
+            inputs.push(match model.node(outlet.node).name.as_str() {
+                "input_ids" => Input::Ids,
+                "attention_mask" => Input::Mask,
+                "token_type_ids" => Input::TypeIds,
+                other => return Err(format!("Unbekannte Modelleingabe '{}'", other)),
+            });
+            let fact = InferenceFact::dt_shape(i64::datum_type(), tvec!(1, MAX_TOKENS));
+            model.set_input_fact(i, fact).map_err(error)?;
+        }
+
+        let plan = model
+            .into_optimized()
+            .and_then(|m| m.into_runnable())
+            .map_err(error)?;
+
+        let mut encoder = Self { plan, tokenizer, inputs, dim: 0 };
+        encoder.dim = encoder.encode("")?.len();
+        Ok(encoder)
+    }
+
+    pub fn encode(&self, text: &str) -> Result<Vec<f32>, String> {
+        let encoding = self.tokenizer.encode(text, true).map_err(|e| e.to_string())?;
+        let len = encoding.get_ids().len().min(MAX_TOKENS);
+        let mask = encoding.get_attention_mask();
+
+        let tensors: TVec<TValue> = self.inputs
+            .iter()
+            .map(|input| {
+                let values = match input {
+                    Input::Ids => encoding.get_ids(),
+                    Input::Mask => mask,
+                    Input::TypeIds => encoding.get_type_ids(),
+                };
+                let mut padded = vec![0i64; MAX_TOKENS];
+                for (p, v) in padded.iter_mut().zip(&values[..len]) {
+                    *p = *v as i64;
+                }
+                let tensor = tract_ndarray::Array2::from_shape_vec((1, MAX_TOKENS), padded)
+                    .expect("Form passt zur Länge");
+                Tensor::from(tensor).into()
+            })
+            .collect();
+
+        let outputs = self.plan.run(tensors).map_err(|e| e.to_string())?;
+        let output = outputs[0].to_array_view::<f32>().map_err(|e| e.to_string())?;
+
+        let mut v = match output.shape() {
+            // Token-Vektoren [1, Tokens, Dim]
+            &[1, _, dim] => {
+                let mut sum = vec![0.0; dim];
+                let mut count = 0.0;
+                let tokens = output.index_axis(tract_ndarray::Axis(0), 0);
+                for (t, token) in tokens.outer_iter().enumerate().take(len) {
+                    if mask[t] == 0 {
+                        continue;
+                    }
+                    for (acc, x) in sum.iter_mut().zip(token.iter()) {
+                        *acc += x;
+                    }
+                    count += 1.0;
+                }
+                if count > 0.0 {
+                    sum.iter_mut().for_each(|x| *x /= count);
+                }
+                sum
+            }
+            // Bereits gepoolter Satzvektor [1, Dim]
+            &[1, _] => output.iter().copied().collect(),
+            shape => return Err(format!("Unerwartete Ausgabeform {:?}", shape)),
+        };
+
+        unit(&mut v);
+        Ok(v)
+    }
+}
+
+impl SentenceEmbedder for SentenceEncoder {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn sentence_vec(&mut self, text: &str) -> Vec<f32> {
+        self.encode(text).unwrap_or_else(|e| {
+            eprintln!("Satzvektor für \"{}\" fehlgeschlagen: {}", text, e);
+            vec![0.0; self.dim]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::similarity::cosine_similarity;
+    use tokenizers::models::wordpiece::WordPiece;
+    use tokenizers::pre_tokenizers::bert::BertPreTokenizer;
+    use tract_onnx::pb;
+
+    const WORDS: [&str; 5] = ["[UNK]", "wetter", "regen", "datei", "öffne"];
+
+    /// Winziges ONNX-Modell: Embedding-Tabelle per `Gather`, Ausgabe
+    /// [1, Tokens, 3] wie `last_hidden_state`
+    fn dummy_model() -> InferenceModel {
+        let int64 = |name: &str| pb::ValueInfoProto {
+            name: name.to_string(),
+            r#type: Some(pb::TypeProto {
+                value: Some(pb::type_proto::Value::TensorType(pb::type_proto::Tensor {
+                    elem_type: pb::tensor_proto::DataType::Int64 as i32,
+                    shape: None,
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let table = pb::TensorProto {
+            name: "table".to_string(),
+            dims: vec![WORDS.len() as i64, 3],
+            data_type: pb::tensor_proto::DataType::Float as i32,
+            float_data: vec![
+                0.0, 0.0, 1.0, // [UNK]
+                1.0, 0.1, 0.0, // wetter
+                0.9, 0.2, 0.0, // regen
+                0.0, 1.0, 0.1, // datei
+                0.1, 1.0, 0.0, // öffne
+            ],
+            ..Default::default()
+        };
+        let gather = pb::NodeProto {
+            op_type: "Gather".to_string(),
+            input: vec!["table".to_string(), "input_ids".to_string()],
+            output: vec!["last_hidden_state".to_string()],
+            ..Default::default()
+        };
+        let proto = pb::ModelProto {
+            ir_version: 7,
+            opset_import: vec![pb::OperatorSetIdProto { domain: String::new(), version: 13 }],
+            graph: Some(pb::GraphProto {
+                node: vec![gather],
+                initializer: vec![table],
+                input: vec![int64("input_ids"), int64("attention_mask")],
+                output: vec![pb::ValueInfoProto { name: "last_hidden_state".to_string(), ..Default::default() }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        tract_onnx::onnx().model_for_proto_model(&proto).unwrap()
+    }
+
+    fn dummy_tokenizer() -> Tokenizer {
+        let vocab: [(String, u32); WORDS.len()] = std::array::from_fn(|i| (WORDS[i].to_string(), i as u32));
+        let model = WordPiece::builder().vocab(vocab).unk_token("[UNK]".to_string()).build().unwrap();
+        let mut tokenizer = Tokenizer::new(model);
+        tokenizer.with_pre_tokenizer(Some(BertPreTokenizer));
+        tokenizer
+    }
+
+    #[test]
+    fn test_dummy_model_embeds_sentences() {
+        let mut encoder = SentenceEncoder::from_model(dummy_model(), dummy_tokenizer()).unwrap();
+        assert_eq!(encoder.dim(), 3);
+
+        let weather = encoder.sentence_vec("wetter regen");
+        let rain = encoder.sentence_vec("regen");
+        let file = encoder.sentence_vec("öffne datei");
+        assert!((weather.iter().map(|x| x * x).sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!(cosine_similarity(&weather, &rain) > 0.9);
+        assert!(cosine_similarity(&weather, &file) < 0.3);
+    }
+}
@@ -1,14 +1,135 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use crate::embeddings::{self, Embeddings};
 use crate::normalize::normalize;
+use crate::synonyms::Thesaurus;
+use serde::{Serialize, Deserialize};
 
+/// Dokumenthäufigkeiten für die IDF-Gewichtung.
+/// Ein Dokument ist eine Beispielphrase (statisch oder gelernt).
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocStats {
+    pub docs: u32,
+    pub df: HashMap<String, u32>,
+}
+
+impl DocStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_document(&mut self, text: &str) {
+        let norm = normalize(text);
+        let words: HashSet<&str> = norm.split_whitespace().collect();
+
+        self.docs += 1;
+        for w in words {
+            *self.df.entry(w.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Nimmt ein Dokument wieder heraus (z.B. beim Zusammenführen
+    /// gelernter Beispiele)
+    pub fn remove_document(&mut self, text: &str) {
+        let norm = normalize(text);
+        let words: HashSet<&str> = norm.split_whitespace().collect();
+
+        self.docs = self.docs.saturating_sub(1);
+        for w in words {
+            if let Some(count) = self.df.get_mut(w) {
+                *count -= 1;
+                if *count == 0 {
+                    self.df.remove(w);
+                }
+            }
+        }
+    }
+
+    /// Geglättete IDF: ln((1 + N) / (1 + df)) + 1. Unbekannte Wörter
+    /// (Tippfehler, Füllwörter außerhalb der Beispiele) bekommen das
+    /// neutrale Gewicht 1, sonst würden sie die Phrase dominieren.
+    pub fn idf(&self, word: &str) -> f32 {
+        match self.df.get(word) {
+            Some(&df) => ((1.0 + self.docs as f32) / (1.0 + df as f32)).ln() + 1.0,
+            None => 1.0,
+        }
+    }
+}
+
+/// Erzeugt Satzvektoren für den Kosinus-Klassifizierer. Die Dimension
+/// hängt vom Verfahren ab (Hashing, Wortvektoren, Transformer).
+pub trait SentenceEmbedder {
+    fn dim(&self) -> usize;
+
+    fn sentence_vec(&mut self, text: &str) -> Vec<f32>;
+
+    /// Dokumenthäufigkeiten für die IDF-Gewichtung, falls das Verfahren
+    /// Wörter gewichtet. Gibt zurück, ob sich dadurch Satzvektoren ändern.
+    fn set_doc_stats(&mut self, _stats: DocStats) -> bool {
+        false
+    }
+}
+
+/// Anteil der Synonyme am Gewicht eines unbekannten Wortes. Kleiner als 1,
+/// damit exakte Treffer immer stärker zählen.
+const SYNONYM_WEIGHT: f32 = 0.6;
+
+/// Standardlängen der Zeichen-n-Gramme
+pub const MIN_N: usize = 3;
+pub const MAX_N: usize = 5;
+
+/// Wortvektoren aus gehashten Zeichen-n-Grammen (fastText-Stil).
+///
+/// Jedes Wort wird mit Randmarkern versehen (`<wetter>`) und in n-Gramme
+/// der Länge `min_n..=max_n` zerlegt. Der Wortvektor ist der Mittelwert der
+/// n-Gramm-Vektoren plus des ganzen Wortes, dadurch landen Tippfehler
+/// ("wettr") und Komposita ("wetterbericht") in der Nähe ihres Grundworts.
 pub struct Vocab {
     vectors: HashMap<String, Vec<f32>>,
     dim: usize,
+    min_n: usize,
+    max_n: usize,
+    stats: DocStats,
+    thesaurus: Thesaurus,
+    /// Vortrainierte Vektoren; die n-Gramm-Vektoren dienen dann nur noch
+    /// für Wörter, die in der Datei fehlen
+    embeddings: Option<Embeddings>,
 }
 
 impl Vocab {
     pub fn new(dim: usize) -> Self {
-        Self { vectors: HashMap::new(), dim }
+        Self {
+            vectors: HashMap::new(),
+            dim,
+            min_n: MIN_N,
+            max_n: MAX_N,
+            stats: DocStats::new(),
+            thesaurus: Thesaurus::default(),
+            embeddings: None,
+        }
+    }
+
+    /// Andere n-Gramm-Längen als `MIN_N..=MAX_N`
+    pub fn with_ngrams(mut self, min_n: usize, max_n: usize) -> Self {
+        self.min_n = min_n.max(1);
+        self.max_n = max_n.max(self.min_n);
+        self.vectors.clear();
+        self
+    }
+
+    /// Nutzt vortrainierte Wortvektoren; die Dimension richtet sich dann
+    /// nach der Datei
+    pub fn with_embeddings(mut self, embeddings: Embeddings) -> Self {
+        self.dim = embeddings.dim();
+        self.vectors.clear();
+        self.embeddings = Some(embeddings);
+        self
+    }
+
+    /// Unbekannte Wörter werden über ihre Synonyme an bekannte angebunden
+    /// ("niederschlag" → "regen")
+    pub fn with_thesaurus(mut self, thesaurus: Thesaurus) -> Self {
+        self.thesaurus = thesaurus;
+        self
     }
 
     fn random_vec(&self, word: &str) -> Vec<f32> {
@@ -27,31 +148,221 @@ impl Vocab {
         }).collect()
     }
 
+    /// Zerlegt ein Wort in Zeichen-n-Gramme inkl. Randmarkern.
+    /// Das ganze Wort (`<wort>`) ist immer enthalten.
+    fn ngrams(&self, word: &str) -> Vec<String> {
+        let marked: Vec<char> = format!("<{}>", word).chars().collect();
+        let mut grams = Vec::new();
+
+        for n in self.min_n..=self.max_n {
+            if n >= marked.len() {
+                break;
+            }
+            for window in marked.windows(n) {
+                grams.push(window.iter().collect());
+            }
+        }
+
+        grams.push(marked.iter().collect());
+        grams
+    }
+
     pub fn word_vec(&mut self, word: &str) -> Vec<f32> {
         if let Some(v) = self.vectors.get(word) {
             return v.clone();
         }
-        let v = self.random_vec(word);
+
+        let v = match self.embeddings.as_mut().and_then(|e| e.get(word)) {
+            Some(v) => v,
+            None => self.ngram_vec(word),
+        };
+
         self.vectors.insert(word.to_string(), v.clone());
         v
     }
 
-    pub fn sentence_vec(&mut self, text: &str) -> Vec<f32> {
+    /// Mittelwert der gehashten n-Gramm-Vektoren. Neben vortrainierten
+    /// Vektoren (Länge 1) auf dieselbe Länge skaliert.
+    fn ngram_vec(&self, word: &str) -> Vec<f32> {
+        let grams = self.ngrams(word);
+        let mut v = vec![0.0; self.dim];
+        for g in &grams {
+            for (acc, x) in v.iter_mut().zip(self.random_vec(g)) {
+                *acc += x;
+            }
+        }
+        for x in v.iter_mut() {
+            *x /= grams.len() as f32;
+        }
+
+        if self.embeddings.is_some() {
+            embeddings::unit(&mut v);
+        }
+        v
+    }
+}
+
+impl SentenceEmbedder for Vocab {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Setzt die Dokumenthäufigkeiten für die IDF-Gewichtung.
+    /// Ohne Statistik sind alle Wörter gleich gewichtet.
+    fn set_doc_stats(&mut self, stats: DocStats) -> bool {
+        let changed = self.stats != stats;
+        self.stats = stats;
+        changed
+    }
+
+    fn sentence_vec(&mut self, text: &str) -> Vec<f32> {
         let norm = normalize(text);
         let words: Vec<&str> = norm.split_whitespace().collect();
 
         let mut sum = vec![0.0; self.dim];
+        if words.is_empty() {
+            return sum;
+        }
+
+        // Füllwörter ("mir", "mal", "bitte") kommen in vielen Phrasen vor
+        // und bekommen dadurch eine niedrige IDF
+        let mut total_weight = 0.0;
         for w in &words {
-            let v = self.word_vec(w);
-            for i in 0..self.dim {
-                sum[i] += v[i];
+            let idf = self.stats.idf(w);
+
+            // Unbekannte Wörter geben einen Teil ihres Gewichts an Synonyme
+            // ab, die in Beispielphrasen vorkommen. Ein Synonym zählt so
+            // immer weniger als der exakte Treffer.
+            let known: Vec<String> = if self.stats.df.contains_key(*w) {
+                Vec::new()
+            } else {
+                self.thesaurus
+                    .synonyms(w)
+                    .filter(|s| self.stats.df.contains_key(*s))
+                    .cloned()
+                    .collect()
+            };
+            let own = if known.is_empty() { 1.0 } else { 1.0 - SYNONYM_WEIGHT };
+
+            let mut weighted = vec![(w.to_string(), idf * own)];
+            for s in known.iter() {
+                weighted.push((s.clone(), idf * SYNONYM_WEIGHT / known.len() as f32));
+            }
+
+            for (word, weight) in weighted {
+                let v = self.word_vec(&word);
+                for (acc, x) in sum.iter_mut().zip(v) {
+                    *acc += x * weight;
+                }
+                total_weight += weight;
             }
         }
 
-        for i in 0..self.dim {
-            sum[i] /= words.len() as f32;
+        for x in sum.iter_mut() {
+            *x /= total_weight;
         }
 
         sum
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::similarity::cosine_similarity;
+
+    #[test]
+    fn test_typo_stays_close() {
+        let mut vocab = Vocab::new(128);
+        let base = vocab.word_vec("wetter");
+        let typo = vocab.word_vec("wettr");
+        let other = vocab.word_vec("datei");
+
+        let close = cosine_similarity(&base, &typo);
+        let far = cosine_similarity(&base, &other);
+        assert!(close > 0.3);
+        assert!(close > far + 0.2);
+    }
+
+    #[test]
+    fn test_idf_downweights_common_words() {
+        let mut stats = DocStats::new();
+        stats.add_document("wie ist das wetter");
+        stats.add_document("wie spät ist es");
+        stats.add_document("öffne die datei");
+
+        let idf = |w: &str| stats.idf(&normalize(w));
+        assert!(idf("wetter") > idf("ist"));
+        assert_eq!(idf("wetter"), idf("datei"));
+        assert!(idf("quasi") <= idf("ist"));
+    }
+
+    #[test]
+    fn test_unknown_filler_does_not_dominate() {
+        let mut stats = DocStats::new();
+        stats.add_document("wie ist das wetter");
+        stats.add_document("wie spät ist es");
+        stats.add_document("öffne die datei");
+        let mut vocab = Vocab::new(64);
+        vocab.set_doc_stats(stats);
+
+        let v = vocab.sentence_vec("wetter quasi");
+        let topic = cosine_similarity(&v, &vocab.sentence_vec("wetter"));
+        let filler = cosine_similarity(&v, &vocab.sentence_vec("quasi"));
+        assert!(topic > filler, "{} <= {}", topic, filler);
+    }
+
+    #[test]
+    fn test_synonyms_pull_towards_known_words() {
+        let mut stats = DocStats::new();
+        stats.add_document("wetter vorhersage regen");
+        stats.add_document("öffne datei programm");
+        let mut thesaurus = Thesaurus::default();
+        thesaurus.add_text("Vorhersage;Prognose");
+
+        let mut plain = Vocab::new(64);
+        plain.set_doc_stats(stats.clone());
+        let mut expanded = Vocab::new(64).with_thesaurus(thesaurus);
+        expanded.set_doc_stats(stats);
+
+        let sim_to = |vocab: &mut Vocab, text: &str| {
+            let sample = vocab.sentence_vec("wetter vorhersage regen");
+            cosine_similarity(&vocab.sentence_vec(text), &sample)
+        };
+        let sim = |vocab: &mut Vocab| sim_to(vocab, "prognose");
+        assert!(sim(&mut expanded) > sim(&mut plain) + 0.2);
+
+        // Der exakte Treffer zählt mehr als das Synonym
+        assert!(sim_to(&mut expanded, "vorhersage") > sim(&mut expanded));
+
+        // Bekannte Wörter werden nicht erweitert
+        let exact = expanded.sentence_vec("vorhersage");
+        assert_eq!(exact, plain.sentence_vec("vorhersage"));
+    }
+
+    #[test]
+    fn test_embeddings_with_fallback() {
+        let path = std::env::temp_dir().join(format!("assistent-{}-vocab.vec", std::process::id()));
+        std::fs::write(&path, "2 4\nwetter 1 0 0 0\nregen 0.9 0.1 0 0\n").unwrap();
+        let embeddings = Embeddings::open(path.to_str().unwrap(), 100).unwrap();
+        let mut vocab = Vocab::new(32).with_embeddings(embeddings);
+
+        assert_eq!(vocab.dim(), 4);
+        let close = cosine_similarity(&vocab.sentence_vec("Wetter"), &vocab.sentence_vec("Regen"));
+        assert!(close > 0.9);
+        // Unbekannte Wörter bekommen einen n-Gramm-Vektor gleicher Länge
+        assert_eq!(vocab.sentence_vec("Datei").len(), 4);
+
+        drop(vocab);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_ngram_range() {
+        let vocab = Vocab::new(8).with_ngrams(2, 3);
+        let grams = vocab.ngrams("ab");
+        assert!(grams.contains(&"<a".to_string()));
+        assert!(grams.contains(&"ab>".to_string()));
+        assert_eq!(grams.last().unwrap(), "<ab>");
+    }
+}